@@ -0,0 +1,121 @@
+// Counting heap allocations, to see which solvers allocate heavily. With the
+// `count-allocs` feature, every allocation in the program goes through
+// Counter, which keeps running totals in a few atomics. Without it, measure()
+// just runs its closure and there is nothing to report.
+//
+// The totals are global, so anything allocating on other threads while a
+// solver is measured (like other tests) is counted too. The solvers' own
+// rayon threads are counted, as they should be.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+/// Whether allocations are being counted.
+pub const ENABLED: bool = cfg!(feature = "count-allocs");
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct Stats {
+    /// How many allocations were made, counting each realloc as one.
+    pub allocs: u64,
+    /// The total size of those allocations.
+    pub bytes: u64,
+    /// The most bytes that were allocated at once, beyond what already was
+    /// when measuring started.
+    pub peak: usize,
+}
+
+static ALLOCS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, plus counting.
+pub struct Counter;
+
+impl Counter {
+    fn add(size: usize) {
+        ALLOCS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size as u64, Ordering::Relaxed);
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for Counter {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Counter::add(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Counter::add(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+            Counter::add(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[cfg(feature = "count-allocs")]
+#[global_allocator]
+static GLOBAL: Counter = Counter;
+
+/// Runs `f`, and returns what it allocated if allocations are being counted.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Stats>) {
+    if !ENABLED {
+        return (f(), None);
+    }
+    let allocs = ALLOCS.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+    let current = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(current, Ordering::Relaxed);
+    let res = f();
+    let stats = Stats {
+        allocs: ALLOCS.load(Ordering::Relaxed) - allocs,
+        bytes: BYTES.load(Ordering::Relaxed) - bytes,
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(current),
+    };
+    (res, Some(stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure() {
+        let (len, stats) = measure(|| {
+            let mut v: Vec<u64> = Vec::with_capacity(1000);
+            v.extend(0..1000);
+            let s = String::from_utf8(vec![b'a'; 100]).unwrap();
+            v.len() + s.len()
+        });
+        assert_eq!(1100, len);
+        if !ENABLED {
+            assert_eq!(None, stats);
+            return;
+        }
+        // Other tests allocate and free at the same time, so the totals are
+        // lower bounds and the peak can't be checked.
+        let stats = stats.unwrap();
+        assert!(stats.allocs >= 2);
+        assert!(stats.bytes >= 8100);
+    }
+}
@@ -0,0 +1,162 @@
+// A single type for the answers of every solver, so that tooling can compare
+// and report them without caring which day produced them.
+
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+use crate::{error, registry};
+
+/// The answer to one part of a puzzle. Most answers are numbers, but a few are
+/// strings (day 17 part 1, day 18 part 2 and day 23 part 2).
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Answer {
+    Int(i128),
+    Text(String),
+}
+
+macro_rules! from_int {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Int(n as i128)
+            }
+        })*
+    };
+}
+
+from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        // Too big to be an Int, but it still has to compare equal to the
+        // same answer read back from text.
+        i128::try_from(n).map_or_else(|_| Answer::Text(n.to_string()), Answer::Int)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// Anything that looks like an integer, ignoring whitespace around it, is an
+/// Int. Anything else is a Text exactly as given, whitespace and all, so that
+/// answers written out with `Display` read back the same.
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(s.trim().parse().map_or_else(|_| Answer::from(s), Answer::Int))
+    }
+}
+
+// Ints are encoded as numbers and Texts as strings.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Answer {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Int(n) => serializer.serialize_i128(*n),
+            Answer::Text(s) => serializer.serialize_str(s),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Answer {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl serde::de::Visitor<'_> for Visitor {
+            type Value = Answer;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "an integer or a string")
+            }
+
+            fn visit_i64<E>(self, n: i64) -> Result<Answer, E> {
+                Ok(Answer::from(n))
+            }
+
+            fn visit_u64<E>(self, n: u64) -> Result<Answer, E> {
+                Ok(Answer::from(n))
+            }
+
+            fn visit_i128<E>(self, n: i128) -> Result<Answer, E> {
+                Ok(Answer::from(n))
+            }
+
+            fn visit_u128<E>(self, n: u128) -> Result<Answer, E> {
+                Ok(Answer::from(n))
+            }
+
+            fn visit_str<E>(self, s: &str) -> Result<Answer, E> {
+                Ok(Answer::from(s))
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+/// Runs `dayN::partM` on `input`, or returns None if there is no such solver.
+pub fn solve(day: u32, part: u32, input: &str) -> Option<Answer> {
+    registry::find(2024, day, part).map(|s| (s.run)(input))
+}
+
+/// Like `solve()`, but runs `dayN::try_partM` so that bad input is reported
+/// instead of panicking.
+pub fn try_solve(day: u32, part: u32, input: &str) -> Option<error::Result<Answer>> {
+    registry::find(2024, day, part).map(|s| s.try_solve(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(Answer::Int(42), Answer::from(42u8));
+        assert_eq!(Answer::Int(-42), Answer::from(-42i32));
+        assert_eq!(Answer::Int(1 << 100), Answer::from(1u128 << 100));
+        assert_eq!(Answer::Text(u128::MAX.to_string()), Answer::from(u128::MAX));
+
+        for answer in [Answer::from(-7i64), Answer::from(u128::MAX), Answer::from("7,0,3"), Answer::from(""), Answer::from(" 7,0 \n")] {
+            assert_eq!(Ok(answer.clone()), answer.to_string().parse());
+        }
+        assert_eq!(Ok(Answer::Int(12)), " 12\n".parse());
+        assert_eq!("30,12", Answer::from("30,12").to_string());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let answers = vec![Answer::from(1u64 << 40), Answer::from(-3i32), Answer::from("a,b")];
+        let json = serde_json::to_string(&answers).unwrap();
+        assert_eq!(r#"[1099511627776,-3,"a,b"]"#, json);
+        assert_eq!(answers, serde_json::from_str::<Vec<Answer>>(&json).unwrap());
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(Some(Answer::Int(2)), solve(2, 1, "7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n1 3 6 7 9"));
+        assert_eq!(Some(Answer::from("7,0,3,1,2,6,3,7,1")), solve(17, 1, include_str!("../input/2024/day17.txt")));
+        assert_eq!(None, solve(26, 1, ""));
+        assert!(try_solve(1, 1, "1 x").unwrap().is_err());
+    }
+}
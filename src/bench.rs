@@ -0,0 +1,189 @@
+// Benchmarking with warmup, percentiles and saved baselines, so that the
+// micro-optimizations in the solvers can be measured and kept honest.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Config {
+    /// How long to run before measuring, to warm up caches and clocks.
+    pub warmup: Duration,
+    /// How long to spend measuring, within the sample limits below.
+    pub measure: Duration,
+    pub min_samples: usize,
+    pub max_samples: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            warmup: Duration::from_millis(200),
+            measure: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10_000,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+    pub mean: Duration,
+}
+
+impl Stats {
+    /// Summarizes a set of timings. `samples` must not be empty.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+        // Nearest-rank percentiles.
+        let percentile = |p: usize| samples[(samples.len() * p).div_ceil(100).max(1) - 1];
+        Stats {
+            samples: samples.len(),
+            min: samples[0],
+            p50: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            max: samples[samples.len() - 1],
+            mean: samples.iter().sum::<Duration>() / samples.len() as u32,
+        }
+    }
+}
+
+/// Times `f` repeatedly. The result of `f` is passed through `black_box` so
+/// that the work can't be optimized away.
+pub fn measure<T>(config: &Config, mut f: impl FnMut() -> T) -> Stats {
+    let start = Instant::now();
+    while start.elapsed() < config.warmup {
+        std::hint::black_box(f());
+    }
+
+    let mut samples = Vec::new();
+    let start = Instant::now();
+    while samples.len() < config.min_samples
+            || (samples.len() < config.max_samples && start.elapsed() < config.measure) {
+        let sample_start = Instant::now();
+        std::hint::black_box(f());
+        samples.push(sample_start.elapsed());
+    }
+    Stats::from_samples(samples)
+}
+
+/// The median time of each benchmark from one run, keyed by a name like
+/// `2024/day14/part1/quad_prod_vec/solve`.
+#[derive(Clone, Default, Eq, PartialEq, Debug)]
+pub struct Baseline {
+    pub medians: BTreeMap<String, Duration>,
+}
+
+/// Where a named baseline is saved.
+pub fn baseline_path(name: &str) -> PathBuf {
+    PathBuf::from(format!("target/aoc24-bench/{}.baseline", name))
+}
+
+impl Baseline {
+    // The format is one `<key> <nanoseconds>` pair per line.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut baseline = Baseline::default();
+        for (i, line) in text.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+            let nanos = line.split_once(' ').and_then(|(key, nanos)| Some((key, nanos.trim().parse().ok()?)));
+            let (key, nanos) = nanos.ok_or_else(|| format!("line {}: expected `<name> <nanoseconds>`", i + 1))?;
+            baseline.medians.insert(key.to_string(), Duration::from_nanos(nanos));
+        }
+        Ok(baseline)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("can't read {}: {}", path.display(), e))?;
+        Baseline::parse(&text).map_err(|e| format!("{}, {}", path.display(), e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let mut text = String::new();
+        for (key, median) in &self.medians {
+            text += &format!("{} {}\n", key, median.as_nanos());
+        }
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("can't create {}: {}", dir.display(), e))?;
+        }
+        std::fs::write(path, text).map_err(|e| format!("can't write {}: {}", path.display(), e))
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Change {
+    pub key: String,
+    pub old: Duration,
+    pub new: Duration,
+    /// The relative change in the median, e.g. 0.1 for 10% slower.
+    pub change: f64,
+}
+
+impl Change {
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change > threshold
+    }
+
+    pub fn is_improvement(&self, threshold: f64) -> bool {
+        self.change < -threshold
+    }
+}
+
+/// Compares every benchmark that is in both baselines.
+pub fn compare(old: &Baseline, new: &Baseline) -> Vec<Change> {
+    new.medians.iter().filter_map(|(key, &new)| {
+        let old = *old.medians.get(key)?;
+        let change = new.as_secs_f64() / old.as_secs_f64().max(1e-12) - 1.0;
+        Some(Change { key: key.clone(), old, new, change })
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples: Vec<Duration> = (1..=100).rev().map(Duration::from_micros).collect();
+        let stats = Stats::from_samples(samples);
+        assert_eq!(100, stats.samples);
+        assert_eq!(Duration::from_micros(1), stats.min);
+        assert_eq!(Duration::from_micros(50), stats.p50);
+        assert_eq!(Duration::from_micros(90), stats.p90);
+        assert_eq!(Duration::from_micros(99), stats.p99);
+        assert_eq!(Duration::from_micros(100), stats.max);
+        assert_eq!(Duration::from_nanos(50_500), stats.mean);
+
+        let stats = Stats::from_samples(vec![Duration::from_nanos(7)]);
+        assert_eq!((stats.min, stats.p50, stats.p99), (stats.max, stats.max, stats.max));
+
+        let config = Config { warmup: Duration::ZERO, measure: Duration::ZERO, min_samples: 3, max_samples: 5 };
+        assert_eq!(3, measure(&config, || 1 + 1).samples);
+    }
+
+    #[test]
+    fn test_baseline() {
+        let mut old = Baseline::default();
+        old.medians.insert("a/solve".to_string(), Duration::from_nanos(1000));
+        old.medians.insert("b/solve".to_string(), Duration::from_nanos(1000));
+        old.medians.insert("c/solve".to_string(), Duration::from_nanos(1000));
+        let text = "a/solve 1200\nb/solve 700\nd/solve 5\n";
+        let new = Baseline::parse(text).unwrap();
+
+        let changes = compare(&old, &new);
+        assert_eq!(vec!["a/solve", "b/solve"], changes.iter().map(|c| c.key.as_str()).collect::<Vec<_>>());
+        assert!(changes[0].is_regression(0.05));
+        assert!(changes[1].is_improvement(0.05));
+        assert!((changes[0].change - 0.2).abs() < 1e-9);
+
+        let path = std::env::temp_dir().join("aoc24-test.baseline");
+        new.save(&path).unwrap();
+        assert_eq!(Ok(new), Baseline::load(&path));
+        assert_eq!(Err("line 1: expected `<name> <nanoseconds>`".to_string()), Baseline::parse("a/solve"));
+    }
+}
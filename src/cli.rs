@@ -0,0 +1,1216 @@
+// The command line interface for the aoc24 binary. This dispatches through the
+// solver registry, so it doesn't depend on cargo-aoc.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use crate::allocs;
+use crate::answer::Answer;
+use crate::bench::{self, Baseline, Stats};
+use crate::diff;
+use crate::gen;
+use crate::json;
+use crate::manifest::{self, Manifest};
+use crate::parallel::{self, Threads};
+use crate::registry::{self, Solved, Solver};
+use crate::scaffold;
+use crate::trace;
+use crate::viz;
+
+const USAGE: &str = "\
+usage: aoc24 <command> [options]
+
+commands:
+    run      Solve puzzles and print the answers (needs --day or --all)
+    verify   Check every implementation against the expected answers for
+             every input in input/<year>/, reporting pass, fail or missing
+    time     Solve puzzles repeatedly and report how long they took
+    bench    Benchmark parsing and solving with warmup and percentiles,
+             comparing alternates and optionally a saved baseline
+    list     List the registered solvers
+    gen      Print a random input for a 2024 day (needs --day)
+    diff     Check the optimized solvers against their reference versions
+             on random inputs, printing a minimized input if they disagree
+    viz      Record frames of a simulation (days 6, 14, 15 and 16, needs
+             --day) as an animated GIF or a directory of PNG or PPM files
+    new-day N
+             Add day N to the latest year (or --year) from the src/day0.rs
+             template, with placeholder input, answers and example files.
+             Run this from the root of the repo.
+
+options:
+    --year N       Only this year (default: every year)
+    --day N        Only this day
+    --part N       Only this part (1 or 2)
+    --all          Every day
+    --name NAME    Only this implementation (default: the main one, or all
+                   of them for time and list)
+    --input PATH   Read the input from PATH, or from stdin if PATH is `-`
+                   (default: input/<year>/dayN.txt)
+    --answers PATH Read the expected answers for verify from PATH
+                   (default: answers/<year>.toml)
+    --stream       Read the input a line or a chunk at a time instead of all
+                   at once, for run and verify (only some days support this)
+    --runs N       How many times to run each solver for time (default: 10)
+    --warmup MS    How long to warm up each benchmark (default: 200)
+    --measure MS   How long to measure each benchmark (default: 1000)
+    --save-baseline NAME
+                   Save the bench results as a baseline called NAME
+    --baseline NAME
+                   Compare the bench results with a saved baseline and fail
+                   if anything got slower
+    --threshold PCT
+                   How much a median has to change by to be reported when
+                   comparing with a baseline (default: 5)
+    --seed N       The seed for gen and diff (default: 0)
+    --scale X      The size of the input from gen, relative to a real input
+                   (default: 1), or the pixels per cell for viz (default: 4)
+    --cases N      How many inputs diff tries for each check (default: 1000)
+    --threads N    How many threads the parallel solvers use (default: one
+                   per core, or RAYON_NUM_THREADS)
+    --serial       Run everything on one thread, the same as --threads 1
+    --trace        Print events from inside the solvers (grid snapshots,
+                   search frontiers, iteration counts and cache hit rates) to
+                   stderr, as JSON lines with --json. Setting AOC24_TRACE=1
+                   (or AOC24_TRACE=json) does the same.
+    --out PATH     Where viz writes the GIF, or the directory for the frames
+                   (default: target/aoc24-viz/dayN.gif or target/aoc24-viz/dayN)
+    --format F     gif, png or ppm, for viz (default: gif)
+    --palette C,C  Colors for viz as rrggbb hex, replacing the defaults for
+                   empty, wall, visited, actor, box and highlight in order
+    --frames N     The most frames viz keeps, evenly spaced (default: 500)
+    --delay MS     How long each GIF frame shows (default: 40)
+    --json         Print JSON instead of plain text
+";
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Command {
+    Run,
+    Verify,
+    Time,
+    Bench,
+    List,
+    Gen,
+    Diff,
+    Viz,
+    NewDay,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Options {
+    pub command: Command,
+    pub year: Option<u32>,
+    pub day: Option<u32>,
+    pub part: Option<u32>,
+    pub all: bool,
+    pub name: Option<String>,
+    pub input: Option<PathBuf>,
+    pub answers: Option<PathBuf>,
+    pub stream: bool,
+    pub runs: usize,
+    pub bench: bench::Config,
+    pub save_baseline: Option<String>,
+    pub baseline: Option<String>,
+    /// In percent.
+    pub threshold: u32,
+    pub seed: u64,
+    pub scale: Option<f64>,
+    pub cases: usize,
+    pub out: Option<PathBuf>,
+    pub viz: viz::Config,
+    pub threads: Threads,
+    pub trace: bool,
+    pub json: bool,
+    /// Where `new-day` adds files and `bench` keeps its baselines, if not the
+    /// current directory. There is no option for it, so this is only for
+    /// callers other than the command line.
+    pub root: Option<PathBuf>,
+}
+
+impl Options {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut args = args.into_iter();
+        let command = match args.next().as_deref() {
+            Some("run") => Command::Run,
+            Some("verify") => Command::Verify,
+            Some("time") => Command::Time,
+            Some("bench") => Command::Bench,
+            Some("list") => Command::List,
+            Some("gen") => Command::Gen,
+            Some("diff") => Command::Diff,
+            Some("viz") => Command::Viz,
+            Some("new-day") => Command::NewDay,
+            Some(c) => return Err(format!("unknown command `{}`", c)),
+            None => return Err("missing command".to_string()),
+        };
+        let mut options = Options {
+            command,
+            year: None,
+            day: None,
+            part: None,
+            all: false,
+            name: None,
+            input: None,
+            answers: None,
+            stream: false,
+            runs: 10,
+            bench: bench::Config::default(),
+            save_baseline: None,
+            baseline: None,
+            threshold: 5,
+            seed: 0,
+            scale: None,
+            cases: 1000,
+            out: None,
+            viz: viz::Config::default(),
+            threads: Threads::Default,
+            trace: false,
+            json: false,
+            root: None,
+        };
+        while let Some(arg) = args.next() {
+            let mut value = |what: &str| {
+                args.next().ok_or_else(|| format!("`{}` needs {}", arg, what))
+            };
+            match arg.as_str() {
+                "--year" => options.year = Some(parse_number(&arg, &value("a year")?)?),
+                "--day" => options.day = Some(parse_number(&arg, &value("a day")?)?),
+                "--part" => options.part = Some(parse_number(&arg, &value("a part")?)?),
+                "--all" => options.all = true,
+                "--name" => options.name = Some(value("a name")?),
+                "--input" => options.input = Some(PathBuf::from(value("a path")?)),
+                "--answers" => options.answers = Some(PathBuf::from(value("a path")?)),
+                "--stream" => options.stream = true,
+                "--runs" => options.runs = parse_number(&arg, &value("a count")?)?,
+                "--warmup" => options.bench.warmup = Duration::from_millis(parse_number(&arg, &value("a time")?)?),
+                "--measure" => options.bench.measure = Duration::from_millis(parse_number(&arg, &value("a time")?)?),
+                "--save-baseline" => options.save_baseline = Some(value("a name")?),
+                "--baseline" => options.baseline = Some(value("a name")?),
+                "--threshold" => options.threshold = parse_number(&arg, &value("a percentage")?)?,
+                "--seed" => options.seed = parse_number(&arg, &value("a seed")?)?,
+                "--scale" => options.scale = Some(parse_number(&arg, &value("a scale")?)?),
+                "--cases" => options.cases = parse_number(&arg, &value("a count")?)?,
+                "--out" => options.out = Some(PathBuf::from(value("a path")?)),
+                "--format" => {
+                    let format = value("a format")?;
+                    options.viz.format = viz::Format::parse(&format)
+                        .ok_or_else(|| format!("`--format` must be gif, png or ppm, not `{}`", format))?;
+                },
+                "--palette" => options.viz.palette = viz::Palette::parse(&value("colors")?)?,
+                "--frames" => options.viz.frames = parse_number(&arg, &value("a count")?)?,
+                "--delay" => {
+                    let ms: u32 = parse_number(&arg, &value("a time")?)?;
+                    options.viz.delay = (ms / 10).try_into().map_err(|_| "`--delay` is too long".to_string())?;
+                },
+                "--threads" => {
+                    let count = value("a count")?;
+                    options.threads = Threads::parse(&count)
+                        .ok_or_else(|| format!("`--threads` needs a count of at least 1, not `{}`", count))?;
+                },
+                "--serial" => options.threads = Threads::Serial,
+                "--trace" => options.trace = true,
+                "--json" => options.json = true,
+                _ if options.command == Command::NewDay && options.day.is_none() && !arg.starts_with('-') =>
+                    options.day = Some(parse_number("new-day", &arg)?),
+                _ => return Err(format!("unknown option `{}`", arg)),
+            }
+        }
+
+        if options.all && options.day.is_some() {
+            return Err("`--all` and `--day` can't be used together".to_string());
+        }
+        if options.command == Command::Run && !options.all && options.day.is_none() {
+            return Err("`run` needs `--day` or `--all`".to_string());
+        }
+        if options.command == Command::Gen && options.day.is_none() {
+            return Err("`gen` needs `--day`".to_string());
+        }
+        if options.command == Command::NewDay && options.day.is_none() {
+            return Err("`new-day` needs a day, like `new-day 24`".to_string());
+        }
+        if options.command == Command::Viz && options.day.is_none() {
+            return Err("`viz` needs `--day`".to_string());
+        }
+        if options.scale.is_some_and(|s| !(s > 0.0 && s.is_finite())) {
+            return Err("`--scale` must be positive".to_string());
+        }
+        if options.command == Command::Viz {
+            if let Some(scale) = options.scale {
+                if scale.fract() != 0.0 || scale > 64.0 {
+                    return Err("`--scale` for `viz` must be a whole number of pixels up to 64".to_string());
+                }
+                options.viz.scale = scale as usize;
+            }
+        }
+        if options.viz.frames == 0 {
+            return Err("`--frames` must be at least 1".to_string());
+        }
+        if options.input.is_some() && options.day.is_none() {
+            return Err("`--input` needs `--day`".to_string());
+        }
+        // The same day can exist in several years, but an input or a
+        // manifest belongs to just one.
+        if (options.input.is_some() || options.answers.is_some()) && options.year.is_none() && registry::years().len() > 1 {
+            return Err("`--input` and `--answers` need `--year`".to_string());
+        }
+        // The generators, reference versions and recorders are for 2024.
+        if matches!(options.command, Command::Gen | Command::Diff | Command::Viz) && options.year.is_some_and(|y| y != 2024) {
+            return Err("`gen`, `diff` and `viz` only have 2024 puzzles".to_string());
+        }
+        if options.part.is_some_and(|p| p != 1 && p != 2) {
+            return Err("`--part` must be 1 or 2".to_string());
+        }
+        if options.stream && !matches!(options.command, Command::Run | Command::Verify) {
+            return Err("`--stream` only works with `run` and `verify`".to_string());
+        }
+        // Each solver reads the input again, and stdin can only be read once.
+        if options.stream && options.input.as_deref() == Some(Path::new("-")) && options.part.is_none() {
+            return Err("`--stream` from stdin needs `--part`".to_string());
+        }
+        if options.runs == 0 {
+            return Err("`--runs` must be at least 1".to_string());
+        }
+        Ok(options)
+    }
+
+    // The solvers this invocation applies to, grouped by day and part.
+    fn solvers(&self) -> Vec<Solver> {
+        let all_names = matches!(self.command, Command::Verify | Command::Time | Command::Bench | Command::List);
+        registry::solvers().into_iter()
+            .filter(|s| self.year.is_none_or(|y| s.year == y))
+            .filter(|s| self.day.is_none_or(|d| s.day == d))
+            .filter(|s| self.part.is_none_or(|p| s.part == p))
+            .filter(|s| !self.stream || s.stream.is_some())
+            .filter(|s| match &self.name {
+                Some(name) => s.name == name,
+                None => all_names || s.is_default(),
+            })
+            .collect()
+    }
+
+    fn threshold(&self) -> f64 {
+        self.threshold as f64 / 100.0
+    }
+
+    // `path` under the root, which is relative to the current directory.
+    fn in_root(&self, path: PathBuf) -> PathBuf {
+        match &self.root {
+            Some(root) => root.join(path),
+            None => path,
+        }
+    }
+}
+
+fn read_input(path: &Path) -> Result<String, String> {
+    if path == Path::new("-") {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input).map_err(|e| format!("can't read stdin: {}", e))?;
+        return Ok(input);
+    }
+    std::fs::read_to_string(path).map_err(|e| format!("can't read {}: {}", path.display(), e))
+}
+
+fn open_input(path: &Path) -> Result<Box<dyn BufRead>, String> {
+    if path == Path::new("-") {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+    let file = std::fs::File::open(path).map_err(|e| format!("can't read {}: {}", path.display(), e))?;
+    Ok(Box::new(BufReader::with_capacity(1 << 16, file)))
+}
+
+fn parse_number<T: std::str::FromStr>(option: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("`{}` needs a number, not `{}`", option, value))
+}
+
+/// Where the input for a day is kept, relative to the root of the repo.
+pub fn default_input(year: u32, day: u32) -> PathBuf {
+    manifest::input_dir(year).join(format!("day{}.txt", day))
+}
+
+// How a verify run compares with the manifest.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+enum Status {
+    Pass,
+    Fail,
+    // There is no expected answer, or no input to check it with.
+    Missing,
+    Error,
+}
+
+impl Status {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Status::Pass => "pass",
+            Status::Fail => "fail",
+            Status::Missing => "missing",
+            Status::Error => "error",
+        }
+    }
+}
+
+const NO_INPUT: &str = "no input found";
+
+// One solver to run on one input.
+struct Job {
+    solver: Solver,
+    input_name: String,
+    input_path: PathBuf,
+}
+
+// The outcome of running one solver on one input.
+struct Report {
+    solver: Solver,
+    input_name: String,
+    answer: Result<Answer, String>,
+    expected: Option<Answer>,
+    // How long parsing took, when it was done once for several parts. Only
+    // the first of them reports it, and `times` leaves it out.
+    parse_time: Option<Duration>,
+    times: Vec<Duration>,
+    // What the first run allocated, with the `count-allocs` feature.
+    parse_allocs: Option<allocs::Stats>,
+    allocs: Option<allocs::Stats>,
+}
+
+impl Report {
+    fn status(&self) -> Status {
+        if self.answer.as_ref().is_err_and(|e| e == NO_INPUT) {
+            return Status::Missing;
+        }
+        match (&self.answer, &self.expected) {
+            (Err(_), _) => Status::Error,
+            (Ok(_), None) => Status::Missing,
+            (Ok(answer), Some(expected)) if answer == expected => Status::Pass,
+            (Ok(_), Some(_)) => Status::Fail,
+        }
+    }
+
+    fn passed(&self, command: Command) -> bool {
+        match command {
+            Command::Verify => matches!(self.status(), Status::Pass | Status::Missing),
+            _ => self.answer.is_ok(),
+        }
+    }
+}
+
+fn json_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Int(n) => n.to_string(),
+        Answer::Text(s) => json::string(s),
+    }
+}
+
+fn json_solver(solver: &Solver) -> String {
+    format!("\"year\": {}, \"day\": {}, \"part\": {}, \"name\": {}",
+        solver.year, solver.day, solver.part, json::string(solver.name))
+}
+
+fn label(solver: &Solver) -> String {
+    // The year is left out while there is only one.
+    let mut res = match registry::years().len() {
+        1 => String::new(),
+        _ => format!("{} ", solver.year),
+    };
+    write!(res, "day {} part {}", solver.day, solver.part).unwrap();
+    if !solver.is_default() {
+        write!(res, " ({})", solver.name).unwrap();
+    }
+    res
+}
+
+// Formats a duration with a unit that keeps it readable.
+pub fn format_duration(d: Duration) -> String {
+    let nanos = d.as_nanos();
+    if nanos < 10_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 10_000_000 {
+        format!("{:.1}µs", nanos as f64 / 1e3)
+    } else if nanos < 10_000_000_000 {
+        format!("{:.1}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
+
+// Formats a size in bytes with a binary unit.
+pub fn format_bytes(bytes: u64) -> String {
+    let units = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < units.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{}B", bytes),
+        _ => format!("{:.1}{}", size, units[unit]),
+    }
+}
+
+fn format_allocs(stats: &allocs::Stats) -> String {
+    format!("{} allocs, {} total, {} peak", stats.allocs, format_bytes(stats.bytes), format_bytes(stats.peak as u64))
+}
+
+fn json_allocs(prefix: &str, stats: &allocs::Stats) -> String {
+    format!(", \"{0}allocs\": {1}, \"{0}alloc_bytes\": {2}, \"{0}peak_bytes\": {3}",
+        prefix, stats.allocs, stats.bytes, stats.peak)
+}
+
+fn run(options: &Options, job: Job, input: &Result<String, String>, manifest: &Manifest) -> Report {
+    let Job { solver, input_name, .. } = job;
+    let expected = manifest.get(&input_name, solver.part).cloned();
+    let mut report = Report {
+        solver, input_name, answer: Err(String::new()), expected,
+        parse_time: None, times: Vec::new(), parse_allocs: None, allocs: None,
+    };
+    let input = match input {
+        Ok(input) => input,
+        Err(e) => {
+            report.answer = Err(e.clone());
+            return report;
+        },
+    };
+    let start = Instant::now();
+    let (answer, allocs) = allocs::measure(|| solver.try_solve(input));
+    report.times.push(start.elapsed());
+    report.answer = answer.map_err(|e| e.to_string());
+    report.allocs = allocs;
+    if options.command == Command::Time && report.answer.is_ok() {
+        // The input is known to be good now, so time the fast path.
+        report.times.clear();
+        for _ in 0..options.runs {
+            let start = Instant::now();
+            std::hint::black_box((solver.run)(std::hint::black_box(input)));
+            report.times.push(start.elapsed());
+        }
+    }
+    report
+}
+
+// Like run(), but hands the solver a reader instead of the whole input.
+fn run_stream(job: Job, manifest: &Manifest) -> Report {
+    let Job { solver, input_name, input_path } = job;
+    let expected = manifest.get(&input_name, solver.part).cloned();
+    let stream = solver.stream.unwrap();
+    let start = Instant::now();
+    let (answer, allocs) = allocs::measure(|| open_input(&input_path)
+        .and_then(|mut input| stream(&mut input).map_err(|e| e.to_string())));
+    Report { solver, input_name, answer, expected, parse_time: None, times: vec![start.elapsed()], parse_allocs: None, allocs }
+}
+
+// Like run(), but takes the answer from a parse shared with the day's other
+// parts.
+fn run_parsed(job: Job, solved: &Result<Solved, String>, manifest: &Manifest) -> Report {
+    let Job { solver, input_name, .. } = job;
+    let expected = manifest.get(&input_name, solver.part).cloned();
+    let mut report = Report {
+        solver, input_name, answer: Err(String::new()), expected,
+        parse_time: None, times: Vec::new(), parse_allocs: None, allocs: None,
+    };
+    match solved {
+        Ok(solved) => {
+            let i = solved.parts.iter().position(|p| p.part == solver.part).unwrap();
+            let part = &solved.parts[i];
+            report.answer = part.answer.clone().map_err(|e| e.to_string());
+            report.times.push(part.time);
+            report.allocs = part.allocs;
+            if i == 0 {
+                report.parse_time = Some(solved.parse_time);
+                report.parse_allocs = solved.parse_allocs;
+            }
+        },
+        Err(e) => report.answer = Err(e.clone()),
+    }
+    report
+}
+
+fn print_list(options: &Options, out: &mut impl Write) -> io::Result<()> {
+    let solvers = options.solvers();
+    if options.json {
+        let items: Vec<String> = solvers.iter().map(|s| format!("{{{}}}", json_solver(s))).collect();
+        writeln!(out, "[{}]", items.join(", "))
+    } else {
+        for s in solvers {
+            writeln!(out, "{} day {} part {} {}", s.year, s.day, s.part, s.name)?;
+        }
+        Ok(())
+    }
+}
+
+fn print_report(options: &Options, report: &Report, out: &mut impl Write) -> io::Result<()> {
+    let total: Duration = report.times.iter().sum();
+    let min = report.times.iter().min().copied().unwrap_or_default();
+    let mean = total.checked_div(report.times.len() as u32).unwrap_or_default();
+    if options.json {
+        let mut res = format!("{{{}, \"input\": {}, ", json_solver(&report.solver), json::string(&report.input_name));
+        match &report.answer {
+            Ok(answer) => write!(res, "\"answer\": {}", json_answer(answer)).unwrap(),
+            Err(e) => write!(res, "\"error\": {}", json::string(e)).unwrap(),
+        }
+        match options.command {
+            Command::Run => {
+                write!(res, ", \"nanos\": {}", total.as_nanos()).unwrap();
+                if let Some(parse_time) = report.parse_time {
+                    write!(res, ", \"parse_nanos\": {}", parse_time.as_nanos()).unwrap();
+                }
+                if let Some(stats) = &report.allocs {
+                    res.push_str(&json_allocs("", stats));
+                }
+                if let Some(stats) = &report.parse_allocs {
+                    res.push_str(&json_allocs("parse_", stats));
+                }
+            },
+            Command::Time => write!(res, ", \"runs\": {}, \"min_nanos\": {}, \"mean_nanos\": {}",
+                report.times.len(), min.as_nanos(), mean.as_nanos()).unwrap(),
+            Command::Verify => {
+                write!(res, ", \"status\": \"{}\"", report.status().as_str()).unwrap();
+                if let Some(expected) = &report.expected {
+                    write!(res, ", \"expected\": {}", json_answer(expected)).unwrap();
+                }
+            },
+            Command::Bench | Command::List | Command::Gen | Command::Diff | Command::Viz | Command::NewDay => unreachable!(),
+        }
+        res.push('}');
+        return write!(out, "{}", res);
+    }
+
+    let answer = match &report.answer {
+        Ok(answer) => answer.to_string(),
+        Err(e) => format!("error: {}", e),
+    };
+    let label = label(&report.solver);
+    match options.command {
+        Command::Run => {
+            match report.parse_time {
+                Some(parse_time) => write!(out, "{}: {} (parse {} + solve {})",
+                    label, answer, format_duration(parse_time), format_duration(total))?,
+                None => write!(out, "{}: {} ({})", label, answer, format_duration(total))?,
+            }
+            match (&report.parse_allocs, &report.allocs) {
+                (Some(parse), Some(solve)) => write!(out, " [parse: {}; solve: {}]", format_allocs(parse), format_allocs(solve))?,
+                (None, Some(stats)) => write!(out, " [{}]", format_allocs(stats))?,
+                _ => {},
+            }
+            writeln!(out)
+        },
+        Command::Time if report.answer.is_ok() => writeln!(out, "{}: min {}, mean {} over {} runs",
+            label, format_duration(min), format_duration(mean), report.times.len()),
+        Command::Time => writeln!(out, "{}: {}", label, answer),
+        Command::Verify => {
+            let status = report.status().as_str();
+            match (report.status(), &report.expected) {
+                (Status::Fail, Some(expected)) => writeln!(out, "{} {} [{}]: {}, expected {}",
+                    status, label, report.input_name, answer, expected),
+                _ => writeln!(out, "{} {} [{}]: {}", status, label, report.input_name, answer),
+            }
+        },
+        Command::Bench | Command::List | Command::Gen | Command::Diff | Command::Viz | Command::NewDay => unreachable!(),
+    }
+}
+
+// The solvers to run and the inputs to run them on.
+fn jobs(options: &Options) -> Vec<Job> {
+    let mut jobs = Vec::new();
+    for solver in options.solvers() {
+        let paths = match (&options.input, options.command) {
+            (Some(path), _) => vec![path.clone()],
+            (None, Command::Verify) => manifest::find_inputs(&manifest::input_dir(solver.year), solver.day),
+            (None, _) => vec![default_input(solver.year, solver.day)],
+        };
+        for path in paths {
+            let input_name = if path == Path::new("-") { "stdin".to_string() } else { manifest::input_name(&path) };
+            jobs.push(Job { solver, input_name, input_path: path });
+        }
+    }
+    jobs
+}
+
+// One benchmark: either just the parsing step or the whole solver.
+struct Bench {
+    solver: Solver,
+    input_name: String,
+    step: &'static str,
+    stats: Stats,
+    // What one more run allocated, with the `count-allocs` feature.
+    allocs: Option<allocs::Stats>,
+}
+
+impl Bench {
+    fn key(&self) -> String {
+        let s = &self.solver;
+        format!("{}/day{}/part{}/{}/{}", s.year, s.day, s.part, s.name, self.step)
+    }
+}
+
+fn print_bench(bench: &Bench, old: Option<Duration>, out: &mut impl Write) -> io::Result<()> {
+    let Stats { samples, min, p50, p90, p99, max, mean } = bench.stats;
+    write!(out, "{{{}, \"input\": {}, \"step\": \"{}\", \"samples\": {}, \"min_nanos\": {}, \"p50_nanos\": {}, \
+                \"p90_nanos\": {}, \"p99_nanos\": {}, \"max_nanos\": {}, \"mean_nanos\": {}",
+        json_solver(&bench.solver), json::string(&bench.input_name), bench.step, samples, min.as_nanos(),
+        p50.as_nanos(), p90.as_nanos(), p99.as_nanos(), max.as_nanos(), mean.as_nanos())?;
+    if let Some(stats) = &bench.allocs {
+        write!(out, "{}", json_allocs("", stats))?;
+    }
+    if let Some(old) = old {
+        write!(out, ", \"baseline_nanos\": {}", old.as_nanos())?;
+    }
+    write!(out, "}}")
+}
+
+// Benchmarks every selected solver on its input, then compares alternates
+// with each other and everything with the baseline, if there is one.
+fn run_benches(options: &Options, out: &mut impl Write) -> io::Result<bool> {
+    let old = match &options.baseline {
+        Some(name) => match Baseline::load(&options.in_root(bench::baseline_path(name))) {
+            Ok(baseline) => Some(baseline),
+            Err(e) => {
+                writeln!(out, "error: {}", e)?;
+                return Ok(false);
+            },
+        },
+        None => None,
+    };
+
+    let mut ok = true;
+    let mut benches = Vec::new();
+    let mut inputs: HashMap<PathBuf, Result<String, String>> = HashMap::new();
+    for job in jobs(options) {
+        let input = inputs.entry(job.input_path.clone()).or_insert_with(|| read_input(&job.input_path));
+        // Only good inputs are benchmarked, so that the fast paths are safe.
+        let checked = input.clone().and_then(|input| job.solver.try_solve(&input).map_err(|e| e.to_string()));
+        let (Ok(input), Ok(_)) = (input, checked.as_ref()) else {
+            if !options.json {
+                writeln!(out, "{}: error: {}", label(&job.solver), checked.unwrap_err())?;
+            }
+            ok = false;
+            continue;
+        };
+        if let Some(parse) = job.solver.parse {
+            let stats = bench::measure(&options.bench, || parse(input));
+            let allocs = allocs::measure(|| parse(input)).1;
+            benches.push(Bench { solver: job.solver, input_name: job.input_name.clone(), step: "parse", stats, allocs });
+        }
+        let stats = bench::measure(&options.bench, || (job.solver.run)(std::hint::black_box(input)));
+        let allocs = allocs::measure(|| (job.solver.run)(input)).1;
+        benches.push(Bench { solver: job.solver, input_name: job.input_name, step: "solve", stats, allocs });
+    }
+
+    let mut new = Baseline::default();
+    for bench in &benches {
+        new.medians.insert(bench.key(), bench.stats.p50);
+    }
+    let old = old.unwrap_or_default();
+
+    if options.json {
+        write!(out, "[")?;
+        for (i, bench) in benches.iter().enumerate() {
+            if i > 0 {
+                write!(out, ",\n ")?;
+            }
+            print_bench(bench, old.medians.get(&bench.key()).copied(), out)?;
+        }
+        writeln!(out, "]")?;
+    } else {
+        for bench in &benches {
+            let stats = &bench.stats;
+            write!(out, "{} {}: p50 {}, p90 {}, p99 {} over {} samples", label(&bench.solver), bench.step,
+                format_duration(stats.p50), format_duration(stats.p90), format_duration(stats.p99), stats.samples)?;
+            match &bench.allocs {
+                Some(stats) => writeln!(out, "; {}", format_allocs(stats))?,
+                None => writeln!(out)?,
+            }
+        }
+
+        // How each implementation of a part compares with the fastest one.
+        let solves: Vec<&Bench> = benches.iter().filter(|b| b.step == "solve").collect();
+        for group in solves.chunk_by(|a, b| (a.solver.day, a.solver.part) == (b.solver.day, b.solver.part)) {
+            let Some(fastest) = group.iter().min_by_key(|b| b.stats.p50).filter(|_| group.len() > 1) else {
+                continue;
+            };
+            for bench in group.iter().filter(|b| b.solver.name != fastest.solver.name) {
+                writeln!(out, "{} is {:.2}x slower than {}", label(&bench.solver),
+                    bench.stats.p50.as_secs_f64() / fastest.stats.p50.as_secs_f64().max(1e-12), fastest.solver.name)?;
+            }
+        }
+    }
+
+    if options.baseline.is_some() {
+        let threshold = options.threshold();
+        let changes = bench::compare(&old, &new);
+        let regressions = changes.iter().filter(|c| c.is_regression(threshold)).count();
+        let improvements = changes.iter().filter(|c| c.is_improvement(threshold)).count();
+        if !options.json {
+            for change in changes.iter().filter(|c| c.is_regression(threshold) || c.is_improvement(threshold)) {
+                let kind = if change.is_regression(threshold) { "regression" } else { "improvement" };
+                writeln!(out, "{} {}: {} -> {} ({:+.1}%)", kind, change.key,
+                    format_duration(change.old), format_duration(change.new), change.change * 100.0)?;
+            }
+            writeln!(out, "{} regressions, {} improvements, {} unchanged",
+                regressions, improvements, changes.len() - regressions - improvements)?;
+        }
+        ok &= regressions == 0;
+    }
+    if let Some(name) = &options.save_baseline {
+        if let Err(e) = new.save(&options.in_root(bench::baseline_path(name))) {
+            writeln!(out, "error: {}", e)?;
+            ok = false;
+        }
+    }
+    Ok(ok)
+}
+
+// Runs every differential check that matches the options, stopping each one at
+// its first counterexample.
+fn run_diffs(options: &Options, out: &mut impl Write) -> io::Result<bool> {
+    let checks: Vec<&diff::Check> = diff::checks().iter()
+        .filter(|c| options.day.is_none_or(|d| c.day == d))
+        .filter(|c| options.part.is_none_or(|p| c.part == p))
+        .collect();
+    if checks.is_empty() {
+        writeln!(out, "no matching checks")?;
+        return Ok(false);
+    }
+    let mut ok = true;
+    if options.json {
+        write!(out, "[")?;
+    }
+    for (i, check) in checks.into_iter().enumerate() {
+        let result = diff::run(check, options.seed, options.cases);
+        ok &= result.is_ok();
+        if options.json {
+            let counterexample = match &result {
+                Ok(_) => "null".to_string(),
+                Err(e) => {
+                    let fast = match &e.fast {
+                        Ok(answer) => format!("\"fast\": {}", json_answer(answer)),
+                        Err(panic) => format!("\"panic\": {}", json::string(panic)),
+                    };
+                    format!("{{\"seed\": {}, \"input\": {}, {}, \"reference\": {}}}",
+                        e.seed, json::string(&e.input), fast, json_answer(&e.reference))
+                },
+            };
+            write!(out, "{}{{\"day\": {}, \"part\": {}, \"name\": {}, \"cases\": {}, \"counterexample\": {}}}",
+                if i > 0 { ",\n " } else { "" }, check.day, check.part, json::string(check.name),
+                options.cases, counterexample)?;
+        } else {
+            match result {
+                Ok(cases) => writeln!(out, "{:?}: {} cases agree", check, cases)?,
+                Err(e) => writeln!(out, "{}", e)?,
+            }
+        }
+    }
+    if options.json {
+        writeln!(out, "]")?;
+    }
+    Ok(ok)
+}
+
+// Records a day's simulation and saves the frames.
+fn run_viz(options: &Options, out: &mut impl Write) -> io::Result<bool> {
+    let day = options.day.unwrap();
+    let Some(record) = viz::recorder(day) else {
+        writeln!(out, "no visualization for day {}", day)?;
+        return Ok(false);
+    };
+    let input_path = options.input.clone().unwrap_or_else(|| default_input(2024, day));
+    let input = match read_input(&input_path) {
+        Ok(input) => input,
+        Err(e) => {
+            writeln!(out, "error: {}", e)?;
+            return Ok(false);
+        },
+    };
+    let mut recorder = viz::Recorder::new(options.viz.frames);
+    if let Err(e) = record(&input, &mut recorder) {
+        writeln!(out, "error: {}", e)?;
+        return Ok(false);
+    }
+    let path = options.out.clone().unwrap_or_else(|| {
+        let name = match options.viz.format {
+            viz::Format::Gif => format!("day{}.gif", day),
+            _ => format!("day{}", day),
+        };
+        Path::new("target/aoc24-viz").join(name)
+    });
+    viz::save(recorder.frames(), &options.viz, &path)?;
+    writeln!(out, "wrote {} frames to {}", recorder.frames().len(), path.display())?;
+    Ok(true)
+}
+
+/// Runs the command described by `options`, writing the results to `out`.
+/// Returns whether everything succeeded.
+pub fn execute(options: &Options, out: &mut impl Write) -> io::Result<bool> {
+    if options.command == Command::List {
+        print_list(options, out)?;
+        return Ok(true);
+    }
+    if options.command == Command::Gen {
+        let day = options.day.unwrap();
+        return match gen::generate(day, options.seed, options.scale.unwrap_or(1.0)) {
+            Some(input) => out.write_all(input.as_bytes()).map(|_| true),
+            None => writeln!(out, "no generator for day {}", day).map(|_| false),
+        };
+    }
+    if options.command == Command::Diff {
+        return run_diffs(options, out);
+    }
+    if options.command == Command::Viz {
+        return run_viz(options, out);
+    }
+    if options.command == Command::NewDay {
+        let year = options.year.or_else(|| registry::years().last().copied()).unwrap();
+        return match scaffold::new_day(options.root.as_deref().unwrap_or(Path::new(".")), year, options.day.unwrap()) {
+            Ok(changes) => changes.iter().try_for_each(|c| writeln!(out, "{}", c)).map(|_| true),
+            Err(e) => writeln!(out, "error: {}", e).map(|_| false),
+        };
+    }
+    if options.solvers().is_empty() {
+        writeln!(out, "no matching solvers")?;
+        return Ok(false);
+    }
+    if options.command == Command::Bench {
+        return run_benches(options, out);
+    }
+    // Each year has its own manifest.
+    let mut manifests: HashMap<u32, Manifest> = HashMap::new();
+    if options.command == Command::Verify {
+        for solver in options.solvers() {
+            if manifests.contains_key(&solver.year) {
+                continue;
+            }
+            let path = options.answers.clone().unwrap_or_else(|| manifest::default_path(solver.year));
+            match Manifest::load(&path) {
+                Ok(manifest) => manifests.insert(solver.year, manifest),
+                Err(e) => {
+                    writeln!(out, "error: {}", e)?;
+                    return Ok(false);
+                },
+            };
+        }
+    }
+    let no_answers = Manifest::default();
+    let answers_for = |year| manifests.get(&year).unwrap_or(&no_answers);
+
+    let jobs = jobs(options);
+    // Parts of days with no input at all are still reported when verifying.
+    let mut missing = Vec::new();
+    if options.command == Command::Verify && options.input.is_none() {
+        for solver in options.solvers() {
+            if !jobs.iter().any(|j| (j.solver.year, j.solver.day) == (solver.year, solver.day)) {
+                missing.push(Report {
+                    solver,
+                    input_name: format!("day{}", solver.day),
+                    answer: Err(NO_INPUT.to_string()),
+                    expected: None,
+                    parse_time: None,
+                    times: Vec::new(),
+                    parse_allocs: None,
+                    allocs: None,
+                });
+            }
+        }
+    }
+
+    // The main solvers parse each input once for all of the day's parts.
+    // `time` runs the parts separately, to time them on their own.
+    let shared = |job: &Job| !options.stream && options.command != Command::Time && job.solver.is_default();
+    let mut parts: HashMap<(u32, u32, PathBuf), Vec<u32>> = HashMap::new();
+    for job in jobs.iter().filter(|job| shared(job)) {
+        parts.entry((job.solver.year, job.solver.day, job.input_path.clone())).or_default().push(job.solver.part);
+    }
+    let mut solved: HashMap<(u32, u32, PathBuf), Result<Solved, String>> = HashMap::new();
+
+    // Inputs are read once each, since stdin can only be read once.
+    let mut inputs: HashMap<PathBuf, Result<String, String>> = HashMap::new();
+    let reports = missing.into_iter().chain(jobs.into_iter().map(|job| {
+        let manifest = answers_for(job.solver.year);
+        if options.stream {
+            return run_stream(job, manifest);
+        }
+        let input = inputs.entry(job.input_path.clone()).or_insert_with(|| read_input(&job.input_path));
+        if shared(&job) {
+            let key = (job.solver.year, job.solver.day, job.input_path.clone());
+            let solved = solved.entry(key).or_insert_with_key(|key| match input {
+                Ok(input) => registry::solve_parsed(key.0, key.1, input, &parts[key]).unwrap()
+                    .map_err(|e| e.to_string()),
+                Err(e) => Err(e.clone()),
+            });
+            return run_parsed(job, solved, manifest);
+        }
+        run(options, job, input, manifest)
+    }));
+
+    let mut ok = true;
+    let mut counts: HashMap<Status, usize> = HashMap::new();
+    if options.json {
+        write!(out, "[")?;
+    }
+    for (i, report) in reports.enumerate() {
+        ok &= report.passed(options.command);
+        *counts.entry(report.status()).or_default() += 1;
+        if options.json && i > 0 {
+            write!(out, ",\n ")?;
+        }
+        print_report(options, &report, out)?;
+    }
+    if options.json {
+        writeln!(out, "]")?;
+    } else if options.command == Command::Verify {
+        let count = |s| counts.get(&s).copied().unwrap_or(0);
+        writeln!(out, "{} passed, {} failed, {} missing, {} errors",
+            count(Status::Pass), count(Status::Fail), count(Status::Missing), count(Status::Error))?;
+    }
+    Ok(ok)
+}
+
+/// The entry point for the binary. `args` should not include the program
+/// name.
+pub fn main(args: impl IntoIterator<Item = String>) -> std::process::ExitCode {
+    let options = match Options::parse(args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            return std::process::ExitCode::from(2);
+        },
+    };
+    let tracer: Option<std::sync::Arc<dyn trace::Tracer>> = match options.trace {
+        true => Some(std::sync::Arc::new(trace::Printer { json: options.json })),
+        false => trace::from_env(),
+    };
+    trace::set_tracer(tracer);
+    if let Err(e) = parallel::set_threads(options.threads) {
+        eprintln!("error: {}", e);
+        return std::process::ExitCode::FAILURE;
+    }
+    match execute(&options, &mut io::stdout().lock()) {
+        Ok(true) => std::process::ExitCode::SUCCESS,
+        Ok(false) => std::process::ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::ExitCode::FAILURE
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    fn output(s: &str) -> (bool, String) {
+        let mut out = Vec::new();
+        let ok = execute(&Options::parse(args(s)).unwrap(), &mut out).unwrap();
+        (ok, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_parse() {
+        let options = Options::parse(args("run --day 7 --part 2 --input - --json")).unwrap();
+        assert_eq!(Command::Run, options.command);
+        assert_eq!((Some(7), Some(2), true), (options.day, options.part, options.json));
+        assert_eq!(Some(PathBuf::from("-")), options.input);
+        assert_eq!(Some(2024), Options::parse(args("verify --year 2024")).unwrap().year);
+        assert_eq!(Some(PathBuf::from("a.toml")), Options::parse(args("verify --answers a.toml")).unwrap().answers);
+        assert!(Options::parse(args("run --day 16 --trace")).unwrap().trace);
+        assert_eq!(Threads::Serial, Options::parse(args("bench --serial")).unwrap().threads);
+        assert_eq!(Threads::Count(4), Options::parse(args("bench --threads 4")).unwrap().threads);
+        assert_eq!(Err("`--threads` needs a count of at least 1, not `0`".to_string()), Options::parse(args("bench --threads 0")));
+
+        assert_eq!(Err("`run` needs `--day` or `--all`".to_string()), Options::parse(args("run")));
+        assert_eq!(Err("`--day` needs a number, not `x`".to_string()), Options::parse(args("run --day x")));
+        assert_eq!(Err("`--input` needs `--day`".to_string()), Options::parse(args("time --input x")));
+        assert_eq!(Err("`--stream` only works with `run` and `verify`".to_string()), Options::parse(args("time --day 1 --stream")));
+        assert_eq!(Err("`--stream` from stdin needs `--part`".to_string()), Options::parse(args("run --day 1 --stream --input -")));
+        assert_eq!(Err("unknown command `jump`".to_string()), Options::parse(args("jump")));
+        assert_eq!(Err("`--part` must be 1 or 2".to_string()), Options::parse(args("list --part 3")));
+        assert_eq!(Err("`gen` needs `--day`".to_string()), Options::parse(args("gen --seed 1")));
+        assert_eq!((Some(2025), Some(24)), Options::parse(args("new-day 24 --year 2025")).map(|o| (o.year, o.day)).unwrap());
+        assert_eq!(Err("`new-day` needs a day, like `new-day 24`".to_string()), Options::parse(args("new-day")));
+        assert_eq!(Err("`new-day` needs a number, not `x`".to_string()), Options::parse(args("new-day x")));
+        assert_eq!(Err("`gen`, `diff` and `viz` only have 2024 puzzles".to_string()),
+            Options::parse(args("gen --year 2023 --day 1")));
+        assert_eq!(Err("`--scale` must be positive".to_string()), Options::parse(args("gen --day 1 --scale 0")));
+        assert_eq!(Err("`--scale` for `viz` must be a whole number of pixels up to 64".to_string()),
+            Options::parse(args("viz --day 6 --scale 1.5")));
+        assert_eq!(Err("`--format` must be gif, png or ppm, not `bmp`".to_string()), Options::parse(args("viz --day 6 --format bmp")));
+        let options = Options::parse(args("viz --day 6 --scale 2 --delay 100 --palette 000000")).unwrap();
+        assert_eq!((2, 10, [0, 0, 0]), (options.viz.scale, options.viz.delay, options.viz.palette.color(viz::EMPTY)));
+    }
+
+    // Runs a command with `root` as the root of the repo.
+    fn output_in(root: &Path, s: &str) -> (bool, String) {
+        let options = Options { root: Some(root.to_path_buf()), ..Options::parse(args(s)).unwrap() };
+        let mut out = Vec::new();
+        let ok = execute(&options, &mut out).unwrap();
+        (ok, String::from_utf8(out).unwrap())
+    }
+
+    // An empty directory for one test's files, which no other test or test run
+    // uses at the same time.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc24-test-cli-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_run() {
+        let (ok, out) = output("run --day 17 --part 1");
+        assert!(ok);
+        assert!(out.starts_with("day 17 part 1: 7,0,3,1,2,6,3,7,1 (parse "));
+
+        assert_eq!((false, "no matching solvers\n".to_string()), output("run --year 2023 --all"));
+
+        let (ok, out) = output("run --day 5 --json");
+        assert!(ok);
+        let lines: Vec<&str> = out.lines().collect();
+        assert!(lines[0].contains(r#""answer": 4774, "nanos": "#) && lines[0].contains(r#", "parse_nanos": "#));
+        assert!(lines[1].contains(r#""answer": 6004, "nanos": "#) && !lines[1].contains("parse_nanos"));
+        assert_eq!(allocs::ENABLED, lines[0].contains(r#", "parse_allocs": "#));
+        assert_eq!(allocs::ENABLED, lines[1].contains(r#", "allocs": "#));
+
+        let (ok, out) = output("run --day 10 --part 1");
+        assert!(ok);
+        assert_eq!(allocs::ENABLED, out.contains("; solve: ") && out.contains(" peak]"));
+
+        let (ok, out) = output("run --day 14 --part 1 --name quad_prod_vec_slow --json");
+        assert!(ok);
+        assert!(out.starts_with(r#"[{"year": 2024, "day": 14, "part": 1, "name": "quad_prod_vec_slow", "input": "day14", "answer": 226179492, "nanos": "#));
+
+        let (ok, out) = output("run --day 2 --input input/2024/day17.txt");
+        assert!(!ok);
+        assert!(out.starts_with("day 2 part 1: error: day 2, line 1, column 1: "));
+
+        let (ok, out) = output("run --all --stream --part 1");
+        assert!(ok);
+        assert_eq!(7, out.lines().count());
+    }
+
+    #[test]
+    fn test_verify() {
+        let (ok, out) = output("verify --day 14 --part 1");
+        assert!(ok);
+        assert_eq!("pass day 14 part 1 [day14]: 226179492\n\
+                    pass day 14 part 1 (quad_prod_vec) [day14]: 226179492\n\
+                    pass day 14 part 1 (quad_prod_vec_slow) [day14]: 226179492\n\
+                    3 passed, 0 failed, 0 missing, 0 errors\n", out);
+
+        let answers = temp_dir("verify").join("answers.toml");
+        std::fs::write(&answers, "[day8]\npart1 = 1\n").unwrap();
+        let (ok, out) = output(&format!("verify --day 8 --answers {}", answers.display()));
+        assert!(!ok);
+        assert_eq!("fail day 8 part 1 [day8]: 398, expected 1\n\
+                    missing day 8 part 2 [day8]: 1333\n\
+                    0 passed, 1 failed, 1 missing, 0 errors\n", out);
+
+        let (ok, out) = output("verify --stream --day 22");
+        assert!(ok);
+        assert!(out.starts_with("pass day 22 part 1 [day22]: "));
+        assert!(out.ends_with("2 passed, 0 failed, 0 missing, 0 errors\n"));
+    }
+
+    #[test]
+    fn test_time() {
+        let (ok, out) = output("time --day 3 --runs 2");
+        assert!(ok);
+        assert_eq!(2, out.matches("over 2 runs").count());
+    }
+
+    #[test]
+    fn test_bench() {
+        let root = temp_dir("bench");
+        let (ok, out) = output_in(&root, "bench --day 14 --part 1 --warmup 0 --measure 0 --save-baseline test-cli");
+        assert!(ok);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(6, lines.len());
+        assert!(lines[0].starts_with("day 14 part 1 parse: p50 "));
+        assert!(lines[1].starts_with("day 14 part 1 solve: p50 "));
+        assert!(lines[3].contains(" over 10 samples"));
+        assert_eq!(allocs::ENABLED, lines[3].ends_with(" peak"));
+        assert!(lines[4].contains(" slower than "));
+        assert!(root.join("target/aoc24-bench/test-cli.baseline").exists());
+
+        let (ok, out) = output_in(&root, "bench --day 14 --part 1 --name quad_prod_vec --warmup 0 --measure 0 \
+                                          --baseline test-cli --threshold 100000 --json");
+        assert!(ok);
+        assert!(out.starts_with(r#"[{"year": 2024, "day": 14, "part": 1, "name": "quad_prod_vec", "input": "day14", "step": "solve", "samples": 10, "#));
+        assert!(out.contains(r#""baseline_nanos": "#));
+
+        let (ok, out) = output_in(&root, "bench --day 3 --baseline no-such-baseline");
+        assert!(!ok);
+        let path = root.join("target/aoc24-bench/no-such-baseline.baseline");
+        assert!(out.starts_with(&format!("error: can't read {}: ", path.display())));
+    }
+
+    #[test]
+    fn test_gen() {
+        let (ok, out) = output("gen --day 22 --seed 3 --scale 0.01");
+        assert!(ok);
+        assert_eq!(gen::generate(22, 3, 0.01).unwrap(), out);
+        assert_eq!(20, out.lines().count());
+    }
+
+    #[test]
+    fn test_diff() {
+        let (ok, out) = output("diff --day 21 --cases 20 --seed 5");
+        assert!(ok);
+        assert_eq!("day 21 part 1 (PART1_LUT): 20 cases agree\n\
+                    day 21 part 2 (PART2_LUT): 20 cases agree\n", out);
+
+        let (ok, out) = output("diff --day 9 --part 1 --cases 5 --json");
+        assert!(ok);
+        assert_eq!("[{\"day\": 9, \"part\": 1, \"name\": \"streaming checksum\", \"cases\": 5, \"counterexample\": null}]\n", out);
+    }
+
+    #[test]
+    fn test_viz() {
+        let gif = temp_dir("viz").join("day6.gif");
+        let (ok, out) = output(&format!("viz --day 6 --frames 10 --scale 1 --out {}", gif.display()));
+        assert!(ok);
+        assert_eq!(format!("wrote 6 frames to {}\n", gif.display()), out);
+        let gif = std::fs::read(&gif).unwrap();
+        assert!(gif.starts_with(b"GIF89a\x82\0\x82\0"));
+        assert_eq!((false, "no visualization for day 3\n".to_string()), output("viz --day 3"));
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = temp_dir("new-day");
+        let (ok, out) = output_in(&root, "new-day 7");
+        assert!(!ok);
+        assert_eq!("error: `new-day` has to run from the root of the repo\n", out);
+
+        std::fs::create_dir_all(root.join("src/y2024")).unwrap();
+        std::fs::write(root.join("src/registry.rs"), "solvers! {\n    2024 => y2024 {\n        day1 = 1;\n    }\n}\n").unwrap();
+        std::fs::write(root.join("src/y2024/mod.rs"), "pub mod day1;\n").unwrap();
+        let (ok, out) = output_in(&root, "new-day 7 --year 2024");
+        assert!(ok);
+        assert!(out.starts_with(&format!("created {}\n", root.join("src/y2024/day7.rs").display())));
+        assert_eq!("pub mod day1;\npub mod day7;\n", std::fs::read_to_string(root.join("src/y2024/mod.rs")).unwrap());
+
+        let (ok, out) = output_in(&root, "new-day 7 --year 2024");
+        assert!(!ok);
+        assert!(out.starts_with("error: ") && out.ends_with("day7.rs already exists\n"));
+    }
+
+    #[test]
+    fn test_list() {
+        let (_, out) = output("list --day 14 --part 1");
+        assert_eq!("2024 day 14 part 1 default\n\
+                    2024 day 14 part 1 quad_prod_vec\n\
+                    2024 day 14 part 1 quad_prod_vec_slow\n", out);
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!("1023B", format_bytes(1023));
+        assert_eq!("1.5KiB", format_bytes(1536));
+        assert_eq!("3.0GiB", format_bytes(3 << 30));
+    }
+}
@@ -1,34 +1,62 @@
+// https://adventofcode.com/__YEAR__/day/__DAY__
 
-// https://adventofcode.com/2024/day/0
+// This is the template that `aoc24 new-day` copies to src/yYYYY/dayN.rs,
+// replacing __YEAR__ and __DAY__. It isn't compiled itself.
 
-#[aoc(day0, part1)]
+use crate::error;
+
+/// The parsed input. Both parts share it.
+pub type Input = Vec<String>;
+
+pub fn parse(input: &str) -> error::Result<Input> {
+    Ok(input.lines().map(String::from).collect())
+}
+
+pub fn solve_part1(input: &Input) -> u64 {
+    input.len() as u64
+}
+
+pub fn solve_part2(input: &Input) -> u64 {
+    input.len() as u64
+}
+
+pub fn try_part1(input: &str) -> error::Result<u64> {
+    Ok(solve_part1(&parse(input)?))
+}
+
+pub fn try_part2(input: &str) -> error::Result<u64> {
+    Ok(solve_part2(&parse(input)?))
+}
+
+#[aoc(day__DAY__, part1)]
 pub fn part1(input: &str) -> u64 {
-    return input.len() as u64;
+    try_part1(input).unwrap()
 }
 
-#[aoc(day0, part2)]
+#[aoc(day__DAY__, part2)]
 pub fn part2(input: &str) -> u64 {
-    return input.len() as u64;
+    try_part2(input).unwrap()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::manifest::expected_in;
 
-    static TEST_INPUT: &str = "\
-        ";
-
+    // The examples in examples/__YEAR__/day__DAY__/ are checked by the tests
+    // that the registry generates. These check the real input against
+    // answers/__YEAR__.toml, skipping parts without an answer yet.
     #[test]
     fn test_part1() {
-        assert_eq!(0, part1(TEST_INPUT));
-
-        // assert_eq!(0, part1(include_str!("../input/2024/day0.txt")));
+        if let Some(answer) = expected_in(__YEAR__, __DAY__, 1) {
+            assert_eq!(answer, part1(include_str!("../../input/__YEAR__/day__DAY__.txt")).into());
+        }
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(0, part2(TEST_INPUT));
-
-        // assert_eq!(0, part2(include_str!("../input/2024/day0.txt")));
+        if let Some(answer) = expected_in(__YEAR__, __DAY__, 2) {
+            assert_eq!(answer, part2(include_str!("../../input/__YEAR__/day__DAY__.txt")).into());
+        }
     }
 }
@@ -0,0 +1,239 @@
+// Differential tests of the optimized solvers against straightforward
+// reference implementations. Each check generates small random inputs, runs
+// both versions on them, and when they disagree shrinks the input down to a
+// minimal counterexample.
+
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use crate::answer::Answer;
+use crate::gen::{self, Rng};
+
+/// An optimized solver and the reference implementation it should agree with.
+#[derive(Copy, Clone)]
+pub struct Check {
+    pub day: u32,
+    pub part: u32,
+    /// What is being checked, like `PART1_LUT`.
+    pub name: &'static str,
+    pub fast: fn(&str) -> Answer,
+    /// Returns None for input that isn't valid, which the fast version is
+    /// never run on.
+    pub reference: fn(&str) -> Option<Answer>,
+    pub generate: fn(&mut Rng) -> String,
+}
+
+impl fmt::Debug for Check {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "day {} part {} ({})", self.day, self.part, self.name)
+    }
+}
+
+static CHECKS: &[Check] = &[
+    Check {
+        day: 9,
+        part: 1,
+        name: "streaming checksum",
+        fast: |input| crate::y2024::day9::part1(input).into(),
+        reference: |input| crate::y2024::day9::reference_part1(input).map(Answer::from),
+        generate: |rng| { let files = rng.range(1, 200) as usize; gen::day9(rng, files) },
+    },
+    Check {
+        day: 9,
+        part: 2,
+        name: "file compaction",
+        fast: |input| crate::y2024::day9::part2(input).into(),
+        reference: |input| crate::y2024::day9::reference_part2(input).map(Answer::from),
+        generate: |rng| { let files = rng.range(1, 200) as usize; gen::day9(rng, files) },
+    },
+    Check {
+        day: 13,
+        part: 1,
+        name: "get_total",
+        fast: |input| crate::y2024::day13::part1(input).into(),
+        reference: |input| crate::y2024::day13::reference_total(input, false).map(Answer::from),
+        generate: |rng| { let machines = rng.range(1, 4) as usize; gen::day13(rng, machines, 0.2) },
+    },
+    Check {
+        day: 13,
+        part: 2,
+        name: "get_total",
+        fast: |input| crate::y2024::day13::part2(input).into(),
+        reference: |input| crate::y2024::day13::reference_total(input, true).map(Answer::from),
+        generate: |rng| { let machines = rng.range(1, 4) as usize; gen::day13(rng, machines, 0.2) },
+    },
+    Check {
+        day: 14,
+        part: 1,
+        name: "quad_prod_vec",
+        fast: |input| crate::y2024::day14::part1_quad_prod_vec(input).into(),
+        reference: |input| crate::y2024::day14::try_part1(input).ok().map(Answer::from),
+        generate: |rng| { let robots = rng.range(10, 30) as usize; gen::day14(rng, robots, 101, 103) },
+    },
+    Check {
+        day: 21,
+        part: 1,
+        name: "PART1_LUT",
+        fast: |input| crate::y2024::day21::part1(input).into(),
+        reference: |input| crate::y2024::day21::reference_complexity(input, 2).map(Answer::from),
+        generate: |rng| { let codes = rng.range(1, 5) as usize; gen::day21(rng, codes) },
+    },
+    Check {
+        day: 21,
+        part: 2,
+        name: "PART2_LUT",
+        fast: |input| crate::y2024::day21::part2(input).into(),
+        reference: |input| crate::y2024::day21::reference_complexity(input, 25).map(Answer::from),
+        generate: |rng| { let codes = rng.range(1, 5) as usize; gen::day21(rng, codes) },
+    },
+    Check {
+        day: 22,
+        part: 2,
+        name: "pattern encoding",
+        fast: |input| crate::y2024::day22::part2(input).into(),
+        reference: |input| crate::y2024::day22::reference_part2(input).map(Answer::from),
+        generate: |rng| { let buyers = rng.range(1, 3) as usize; gen::day22(rng, buyers) },
+    },
+];
+
+/// Every differential check, ordered by day and part.
+pub fn checks() -> &'static [Check] {
+    CHECKS
+}
+
+/// An input on which a check's two versions disagree.
+#[derive(Clone, Debug)]
+pub struct Counterexample {
+    pub check: Check,
+    /// The seed that generated the original input.
+    pub seed: u64,
+    /// The input after minimizing.
+    pub input: String,
+    /// What the fast version returned, or its panic message.
+    pub fast: Result<Answer, String>,
+    pub reference: Answer,
+}
+
+impl fmt::Display for Counterexample {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{:?} disagrees with its reference on seed {}", self.check, self.seed)?;
+        match &self.fast {
+            Ok(answer) => writeln!(f, "fast: {}", answer)?,
+            Err(e) => writeln!(f, "fast: panicked: {}", e)?,
+        }
+        writeln!(f, "reference: {}", self.reference)?;
+        write!(f, "minimized input:\n{}", self.input)
+    }
+}
+
+// Runs the fast version, turning a panic into its message.
+fn run_fast(check: &Check, input: &str) -> Result<Answer, String> {
+    panic::catch_unwind(AssertUnwindSafe(|| (check.fast)(input))).map_err(|e| {
+        e.downcast_ref::<&str>().map(|s| s.to_string())
+            .or_else(|| e.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string())
+    })
+}
+
+// Whether the two versions disagree on a valid input.
+fn disagree(check: &Check, input: &str) -> Option<(Result<Answer, String>, Answer)> {
+    let reference = (check.reference)(input)?;
+    let fast = run_fast(check, input);
+    (fast.as_ref() != Ok(&reference)).then_some((fast, reference))
+}
+
+/// Runs `check` on `cases` generated inputs. The inputs come from seeds drawn
+/// from `seed`, and a counterexample reports the seed that `check.generate`
+/// made its original input from. Returns how many cases agreed.
+pub fn run(check: &Check, seed: u64, cases: usize) -> Result<usize, Box<Counterexample>> {
+    let mut seeds = Rng::new(seed);
+    for _ in 0..cases {
+        let seed = seeds.next_u64();
+        let input = (check.generate)(&mut Rng::new(seed));
+        if disagree(check, &input).is_some() {
+            let input = minimize(&input, |input| disagree(check, input).is_some());
+            let (fast, reference) = disagree(check, &input).unwrap();
+            return Err(Box::new(Counterexample { check: *check, seed, input, fast, reference }));
+        }
+    }
+    Ok(cases)
+}
+
+/// Shrinks `input` while `fails` stays true for it: first by removing
+/// blank-line separated blocks, then lines, then single characters.
+pub fn minimize(input: &str, mut fails: impl FnMut(&str) -> bool) -> String {
+    let mut input = input.to_string();
+    for sep in ["\n\n", "\n", ""] {
+        input = shrink(&input, sep, &mut fails);
+    }
+    input
+}
+
+// Removes runs of parts split by `sep`, halving the length of the runs each
+// time no more can be removed.
+fn shrink(input: &str, sep: &str, fails: &mut impl FnMut(&str) -> bool) -> String {
+    let mut parts: Vec<String> = if sep.is_empty() {
+        input.chars().map(String::from).collect()
+    } else {
+        input.split(sep).map(String::from).collect()
+    };
+    let mut len = (parts.len() / 2).max(1);
+    loop {
+        let mut i = 0;
+        while i < parts.len() && parts.len() > 1 {
+            let end = (i + len).min(parts.len());
+            let candidate = [&parts[..i], &parts[end..]].concat();
+            if fails(&candidate.join(sep)) {
+                parts = candidate;
+            } else {
+                i += len;
+            }
+        }
+        if len == 1 {
+            break;
+        }
+        len /= 2;
+    }
+    parts.join(sep)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minimize() {
+        let input = "ab\ncd\n\nexe\nfg\n";
+        assert_eq!("x", minimize(input, |s| s.contains('x')));
+        assert_eq!("ee", minimize(input, |s| s.matches('e').count() == 2));
+        assert_eq!(input, minimize(input, |s| s == input));
+    }
+
+    #[test]
+    fn test_counterexample() {
+        // A deliberately broken version of day 21, which gets one code wrong.
+        let check = Check {
+            fast: |input| Answer::from(crate::y2024::day21::part1(&input.replace("379A", "029A"))),
+            ..checks().iter().find(|c| c.name == "PART1_LUT").copied().unwrap()
+        };
+        let err = (1..).find_map(|seed| run(&check, seed, 100).err()).unwrap();
+        assert_eq!("379A", err.input.trim());
+        assert_eq!(Answer::from(64 * 379u64), err.reference);
+        assert_eq!(Ok(Answer::from(68 * 29u64)), err.fast);
+        assert!(err.to_string().contains("minimized input:\n379A"));
+    }
+
+    #[test]
+    fn test_checks() {
+        for check in checks() {
+            // The day 14 and 22 references are too slow in debug builds for
+            // thousands of cases.
+            let cases = match check.day {
+                14 | 22 => 100,
+                _ => 2000,
+            };
+            if let Err(e) = run(check, 0, cases) {
+                panic!("{}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,145 @@
+// Diagnostics for malformed puzzle input.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// A problem with the puzzle input, pointing at where it was found.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Error {
+    pub day: u32,
+    /// 1-based, or 0 if the problem isn't tied to a single line.
+    pub line: usize,
+    /// 1-based byte offset into the line, or 0 if the problem is the whole
+    /// line.
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn new(day: u32, line: usize, column: usize, expected: impl Into<String>, found: impl Into<String>) -> Self {
+        Error {
+            day,
+            line,
+            column,
+            expected: expected.into(),
+            found: found.into(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "day {}", self.day)?;
+        if self.line != 0 {
+            write!(f, ", line {}", self.line)?;
+        }
+        if self.column != 0 {
+            write!(f, ", column {}", self.column)?;
+        }
+        write!(f, ": expected {}, found {}", self.expected, self.found)
+    }
+}
+
+impl std::error::Error for Error {}
+
+// Quote a snippet of input for an error message, keeping it short.
+fn snippet(part: &str) -> String {
+    if part.is_empty() {
+        return "end of line".to_string();
+    }
+    match part.char_indices().nth(16) {
+        Some((i, _)) => format!("`{}...`", &part[..i]),
+        None => format!("`{}`", part),
+    }
+}
+
+/// A line of puzzle input that remembers where it came from, so that parse
+/// failures can point at the offending text.
+#[derive(Copy, Clone, Debug)]
+pub struct Line<'a> {
+    pub day: u32,
+    /// 1-based.
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// An error pointing at `part`, which should be a slice of this line. An
+    /// empty `part` means that the line ended too early.
+    pub fn error(&self, part: &str, expected: impl Into<String>) -> Error {
+        let start = self.text.as_ptr() as usize;
+        let offset = (part.as_ptr() as usize).wrapping_sub(start);
+        let column = if offset <= self.text.len() { offset + 1 } else { 0 };
+        Error::new(self.day, self.number, column, expected, snippet(part))
+    }
+
+    /// An error for the whole line.
+    pub fn error_line(&self, expected: impl Into<String>) -> Error {
+        Error::new(self.day, self.number, 0, expected, snippet(self.text))
+    }
+
+    pub fn parse<T: FromStr>(&self, part: &str, expected: impl Into<String>) -> Result<T> {
+        part.parse().map_err(|_| self.error(part, expected))
+    }
+
+    /// Unwraps one of the pieces from something like `split()`, reporting the
+    /// end of the line if it is missing.
+    pub fn field(&self, part: Option<&'a str>, expected: impl Into<String>) -> Result<&'a str> {
+        part.ok_or_else(|| self.error(&self.text[self.text.len()..], expected))
+    }
+
+    /// Strips `prefix` from `rest`, which should be a tail of this line.
+    pub fn expect(&self, rest: &'a str, prefix: &str) -> Result<&'a str> {
+        rest.strip_prefix(prefix).ok_or_else(|| self.error(rest, format!("`{}`", prefix)))
+    }
+
+    /// Splits `rest` at the first `delim`.
+    pub fn split_once(&self, rest: &'a str, delim: &str) -> Result<(&'a str, &'a str)> {
+        rest.split_once(delim).ok_or_else(|| self.error(&rest[rest.len()..], format!("`{}`", delim)))
+    }
+}
+
+/// Iterates over the lines of `input`, numbered for error reporting.
+pub fn lines(day: u32, input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(move |(i, text)| Line { day, number: i + 1, text })
+}
+
+/// An error for input that stopped before something that was required.
+pub fn end_of_input(day: u32, input: &str, expected: impl Into<String>) -> Error {
+    Error::new(day, input.lines().count() + 1, 0, expected, "end of input")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_errors() {
+        let line = lines(7, "12: 3 4\n5 6").nth(1).unwrap();
+        assert_eq!(2, line.number);
+        let (res, _) = line.text.split_at(1);
+        assert_eq!(Ok(5), line.parse::<u64>(res, "a number"));
+
+        let err = line.split_once(line.text, ":").unwrap_err();
+        assert_eq!("day 7, line 2, column 4: expected `:`, found end of line", err.to_string());
+
+        let err = line.expect(&line.text[2..], "x").unwrap_err();
+        assert_eq!("day 7, line 2, column 3: expected `x`, found `6`", err.to_string());
+
+        let err = line.parse::<u8>("oops", "a number").unwrap_err();
+        assert_eq!(0, err.column);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("day 3: expected a map, found end of input",
+            Error::new(3, 0, 0, "a map", "end of input").to_string());
+        assert_eq!("day 3, line 4: expected a map, found end of input",
+            end_of_input(3, "a\nb\nc", "a map").to_string());
+        assert_eq!("day 1, line 1: expected two numbers, found `0123456789abcdef...`",
+            lines(1, "0123456789abcdefghij").next().unwrap().error_line("two numbers").to_string());
+    }
+}
@@ -0,0 +1,211 @@
+// The examples from the puzzle descriptions, kept as data files instead of
+// string literals in the tests. Each example is a pair of files in
+// examples/<year>/dayN/: `<name>.txt` with the input, and `<name>.toml` with
+// its answers as `partN = <integer or string>` lines. An example can leave out
+// a part that it has no answer for.
+//
+// Any other `name = <integer>` line is a parameter for the solvers, like the
+// smaller grid that an example is played on. When the puzzle gives several
+// answers for one input with different parameters, each goes in a `[case]`
+// table of its own, which also gets the parameters from the top of the file.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use crate::answer::Answer;
+use crate::manifest;
+use crate::registry::{self, Params};
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Example {
+    /// The file name without the `.txt`, followed by the case in brackets if
+    /// there is one.
+    pub name: String,
+    pub input: String,
+    pub params: Params,
+    answers: BTreeMap<u32, Answer>,
+}
+
+impl Example {
+    /// The expected answer for one part, if the example has one.
+    pub fn answer(&self, part: u32) -> Option<&Answer> {
+        self.answers.get(&part)
+    }
+}
+
+/// Where the examples for a day are kept, relative to the root of the repo.
+pub fn dir(year: u32, day: u32) -> PathBuf {
+    PathBuf::from(format!("examples/{}/day{}", year, day))
+}
+
+// The parameters and answers for one run of an example.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+struct Case {
+    name: Option<String>,
+    params: Params,
+    answers: BTreeMap<u32, Answer>,
+}
+
+// Parses the file that goes with an example into its cases. The top of the file
+// is a case of its own if it has any answers, or if there are no tables.
+fn parse_cases(text: &str) -> Result<Vec<Case>, String> {
+    let mut cases = vec![Case::default()];
+    for (i, line) in text.lines().enumerate() {
+        let error = |what: &str| format!("line {}: {}", i + 1, what);
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name.strip_suffix(']').filter(|n| !n.is_empty()).ok_or_else(|| error("expected `[<case>]`"))?;
+            if cases.iter().any(|c| c.name.as_deref() == Some(name)) {
+                return Err(error("duplicate case"));
+            }
+            // Each case starts from the parameters at the top of the file.
+            cases.push(Case { name: Some(name.to_string()), params: cases[0].params.clone(), answers: BTreeMap::new() });
+            continue;
+        }
+        let case = cases.last_mut().unwrap();
+        let (key, value) = line.split_once('=').ok_or_else(|| error("expected `<name> = <value>`"))?;
+        let (key, value) = (key.trim(), value.trim());
+        let part: u32 = match key {
+            "part1" => 1,
+            "part2" => 2,
+            _ => {
+                let value = value.parse().map_err(|_| error("expected an integer parameter"))?;
+                case.params.set(key, value);
+                continue;
+            }
+        };
+        let answer = manifest::parse_value(value).ok_or_else(|| error("expected an integer or a quoted string"))?;
+        if case.answers.insert(part, answer).is_some() {
+            return Err(error("duplicate answer"));
+        }
+    }
+    if cases.len() > 1 && cases[0].answers.is_empty() {
+        cases.remove(0);
+    }
+    Ok(cases)
+}
+
+/// Every example in `dir`, sorted by name.
+pub fn load_dir(dir: &Path) -> Result<Vec<Example>, String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Ok(Vec::new());
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|e| e == "txt"))
+        .collect();
+    paths.sort();
+    paths.into_iter().map(|path| {
+        let read = |path: &Path| std::fs::read_to_string(path).map_err(|e| format!("can't read {}: {}", path.display(), e));
+        let cases_path = path.with_extension("toml");
+        let cases = match cases_path.exists() {
+            true => parse_cases(&read(&cases_path)?).map_err(|e| format!("{}, {}", cases_path.display(), e))?,
+            false => vec![Case::default()],
+        };
+        let (name, input) = (manifest::input_name(&path), read(&path)?);
+        Ok(cases.into_iter().map(|case| Example {
+            name: match case.name {
+                Some(case) => format!("{} [{}]", name, case),
+                None => name.clone(),
+            },
+            input: input.clone(),
+            params: case.params,
+            answers: case.answers,
+        }).collect::<Vec<_>>())
+    }).collect::<Result<Vec<_>, String>>().map(|examples| examples.concat())
+}
+
+/// Every example for a day, sorted by name.
+pub fn load(year: u32, day: u32) -> Result<Vec<Example>, String> {
+    load_dir(&dir(year, day))
+}
+
+/// Runs every example for a day through the main solvers in the registry and
+/// checks their answers, reporting each one that is wrong. Examples without
+/// parameters are parsed once for both parts, like `aoc24 run` does.
+pub fn check(year: u32, day: u32) -> Result<(), String> {
+    let examples = load(year, day)?;
+    if examples.is_empty() {
+        return Err(format!("no examples in {}", dir(year, day).display()));
+    }
+    let mut failures = Vec::new();
+    for example in &examples {
+        let parts: Vec<u32> = example.answers.keys().copied().collect();
+        let answers: Vec<Result<Answer, String>> = if example.params.is_empty() {
+            match registry::solve_parsed(year, day, &example.input, &parts) {
+                Some(Ok(solved)) => solved.parts.into_iter().map(|p| p.answer.map_err(|e| e.to_string())).collect(),
+                Some(Err(e)) => vec![Err(e.to_string()); parts.len()],
+                None => return Err(format!("{} day {} isn't registered", year, day)),
+            }
+        } else {
+            parts.iter().map(|&part| match registry::find(year, day, part) {
+                Some(solver) => solver.solve_with(&example.input, &example.params).map_err(|e| e.to_string()),
+                None => Err("no solver".to_string()),
+            }).collect()
+        };
+        for (part, answer) in parts.iter().zip(answers) {
+            let expected = &example.answers[part];
+            match answer {
+                Ok(answer) if answer == *expected => {},
+                Ok(answer) => failures.push(format!("`{}` part {}: expected {}, got {}", example.name, part, expected, answer)),
+                Err(e) => failures.push(format!("`{}` part {}: {}", example.name, part, e)),
+            }
+        }
+    }
+    match failures.is_empty() {
+        true => Ok(()),
+        false => Err(format!("{} day {}:\n{}", year, day, failures.join("\n"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cases() {
+        let cases = parse_cases("# From the puzzle\npart1 = 11\npart2 = \"a,b\"\n").unwrap();
+        assert_eq!(1, cases.len());
+        assert_eq!(vec![(1, Answer::Int(11)), (2, Answer::from("a,b"))], cases[0].answers.clone().into_iter().collect::<Vec<_>>());
+        assert!(cases[0].params.is_empty());
+        assert_eq!(Err("line 1: expected an integer parameter".to_string()), parse_cases("part3 = x"));
+        assert_eq!(Err("line 2: duplicate answer".to_string()), parse_cases("part1 = 1\npart1 = 2"));
+
+        // Tables are cases that share the parameters above them.
+        let cases = parse_cases("dim = 7\n[a]\ncount = 2\npart1 = 3\n[b]\npart2 = 4").unwrap();
+        assert_eq!(vec![Some("a"), Some("b")], cases.iter().map(|c| c.name.as_deref()).collect::<Vec<_>>());
+        assert_eq!((Ok(7), Ok(2)), (cases[0].params.get(0, "dim", 0), cases[0].params.get(0, "count", 0)));
+        assert_eq!((Ok(7), Ok(0)), (cases[1].params.get(0, "dim", 0), cases[1].params.get(0, "count", 0)));
+        assert_eq!(3, parse_cases("part1 = 1\n[a]\n[b]").unwrap().len());
+        assert_eq!(Err("line 2: duplicate case".to_string()), parse_cases("[a]\n[a]"));
+        assert_eq!(Err("line 1: expected `[<case>]`".to_string()), parse_cases("[a"));
+    }
+
+    #[test]
+    fn test_load() {
+        let dir = std::env::temp_dir().join("aoc24-test-examples");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("b.txt"), "1\n2\n").unwrap();
+        std::fs::write(dir.join("b.toml"), "part2 = 3\nsize = 5\n[small]\nsize = 2\npart1 = 1\n").unwrap();
+        std::fs::write(dir.join("a.txt"), "").unwrap();
+        let examples = load_dir(&dir).unwrap();
+        assert_eq!(vec!["a", "b", "b [small]"], examples.iter().map(|e| e.name.as_str()).collect::<Vec<_>>());
+        assert_eq!((None, Some(&Answer::Int(3))), (examples[1].answer(1), examples[1].answer(2)));
+        assert_eq!((Some(&Answer::Int(1)), None), (examples[2].answer(1), examples[2].answer(2)));
+        assert_eq!(Ok(2), examples[2].params.get(0, "size", 0));
+        assert_eq!("1\n2\n", examples[2].input);
+
+        std::fs::write(dir.join("a.toml"), "part1 = x").unwrap();
+        assert!(load_dir(&dir).unwrap_err().ends_with("a.toml, line 1: expected an integer or a quoted string"));
+        assert_eq!(Ok(Vec::new()), load_dir(&dir.join("missing")));
+    }
+
+    #[test]
+    fn test_check() {
+        assert_eq!(Ok(()), check(2024, 18));
+        assert_eq!(Err("no examples in examples/2024/day26".to_string()), check(2024, 26));
+    }
+}
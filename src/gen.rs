@@ -0,0 +1,718 @@
+// Seeded generators for random puzzle inputs. Every generated input is valid:
+// it parses, and the solvers for its day can find an answer. They are meant
+// for stress tests, differential tests and benchmarks at sizes beyond the real
+// inputs.
+//
+// Each day has a function with its own size knobs. generate() picks knobs that
+// match the size of a real input, multiplied by a scale factor.
+
+use std::collections::HashSet;
+use crate::grid::{Direction, Grid, Pos};
+
+/// A small, fast, seedable pseudo-random number generator (SplitMix64). The
+/// same seed always gives the same sequence, on every platform.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`. `n` must not be zero.
+    pub fn below(&mut self, n: u64) -> u64 {
+        // Multiply-shift, which is close enough to uniform for test inputs.
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    /// A number in `lo..=hi`.
+    pub fn range(&mut self, lo: i64, hi: i64) -> i64 {
+        lo + self.below((hi - lo + 1) as u64) as i64
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+// Scales a count, like a number of lines.
+fn count(base: usize, scale: f64) -> usize {
+    ((base as f64 * scale).round() as usize).max(1)
+}
+
+// Scales the side of a grid, so that its area scales by `scale`.
+fn side(base: usize, scale: f64) -> usize {
+    ((base as f64 * scale.sqrt()).round() as usize).max(1)
+}
+
+/// Generates an input for `day` that is roughly `scale` times the size of a
+/// real input. Days whose solvers expect a fixed size (14, 17, 18 and 21)
+/// ignore `scale` where it would make the input invalid. Returns None for days
+/// that aren't implemented.
+pub fn generate(day: u32, seed: u64, scale: f64) -> Option<String> {
+    let rng = &mut Rng::new(seed);
+    let input = match day {
+        1 => day1(rng, count(1000, scale), 99999),
+        2 => day2(rng, count(1000, scale), 8),
+        3 => day3(rng, count(6, scale), 120),
+        4 => day4(rng, side(140, scale), side(140, scale)),
+        5 => day5(rng, 49, count(200, scale)),
+        6 => day6(rng, side(130, scale), side(130, scale), 0.05),
+        7 => day7(rng, count(850, scale), 12),
+        8 => day8(rng, side(50, scale), side(50, scale), 40, 4),
+        9 => day9(rng, count(10000, scale)),
+        10 => day10(rng, side(55, scale), side(55, scale)),
+        11 => day11(rng, count(8, scale), 10_000_000),
+        12 => day12(rng, side(140, scale), side(140, scale), count(600, scale), 0),
+        13 => day13(rng, count(320, scale), 0.0),
+        14 => day14(rng, count(500, scale).min(2000), 101, 103),
+        15 => day15(rng, side(50, scale), side(50, scale), count(20000, scale)),
+        16 => day16(rng, side(141, scale), side(141, scale)),
+        17 => day17(rng),
+        18 => day18(rng, 71, 1024),
+        19 => day19(rng, 450, 8, count(400, scale)),
+        20 => day20(rng, side(141, scale), side(141, scale)),
+        21 => day21(rng, 5),
+        22 => day22(rng, count(2000, scale)),
+        23 => day23(rng, count(520, scale).clamp(13, 676), 13, 13),
+        _ => return None,
+    };
+    Some(input)
+}
+
+/// Two columns of location IDs in `1..=max_id`.
+pub fn day1(rng: &mut Rng, lines: usize, max_id: u32) -> String {
+    let mut res = String::new();
+    for _ in 0..lines {
+        let a = rng.range(1, max_id as i64);
+        // Reuse IDs often enough for the similarity score to matter.
+        let b = if rng.chance(0.3) { a } else { rng.range(1, max_id as i64) };
+        res += &format!("{}   {}\n", a, b);
+    }
+    res
+}
+
+/// Reports of 5 to `max_len` levels. Most are safe or one bad level away from
+/// safe.
+pub fn day2(rng: &mut Rng, reports: usize, max_len: usize) -> String {
+    let mut res = String::new();
+    for _ in 0..reports {
+        let len = rng.range(5, max_len.max(5) as i64) as usize;
+        let dir = if rng.chance(0.5) { 1 } else { -1 };
+        let mut levels = vec![rng.range(20, 80)];
+        for _ in 1..len {
+            levels.push(levels[levels.len() - 1] + dir * rng.range(1, 3));
+        }
+        for _ in 0..rng.below(3) {
+            let i = rng.below(len as u64) as usize;
+            levels[i] += rng.range(-4, 4);
+        }
+        let levels: Vec<String> = levels.iter().map(|l| l.to_string()).collect();
+        res += &levels.join(" ");
+        res.push('\n');
+    }
+    res
+}
+
+/// Corrupted memory with about `muls` instructions per line, some of them
+/// corrupted, mixed with `do()` and `don't()`.
+pub fn day3(rng: &mut Rng, lines: usize, muls: usize) -> String {
+    const JUNK: &[u8] = b"!@#$%^&*()[]{}<>,;:'?/+- _~whatselectfromwherewhyhowdonmul";
+    let mut res = String::new();
+    for _ in 0..lines {
+        for _ in 0..muls {
+            for _ in 0..rng.below(12) {
+                res.push(*rng.pick(JUNK) as char);
+            }
+            let (a, b) = (rng.range(1, 999), rng.range(1, 999));
+            res += &match rng.below(10) {
+                0 => "do()".to_string(),
+                1 => "don't()".to_string(),
+                2 => format!("mul[{},{}]", a, b),
+                3 => format!("mul({}, {})", a, b),
+                4 => format!("mul({}{},{})", a, rng.range(0, 9), b),
+                5 => format!("mul({},{}", a, b),
+                _ => format!("mul({},{})", a, b),
+            };
+        }
+        res.push('\n');
+    }
+    res
+}
+
+/// A word search made of the letters in XMAS.
+pub fn day4(rng: &mut Rng, width: usize, height: usize) -> String {
+    Grid::from_fn(width, height, |_| *rng.pick(b"XMAS")).to_string()
+}
+
+/// Ordering rules for every pair of `pages` page numbers, and updates with an
+/// odd number of distinct pages. About half of the updates are in order.
+pub fn day5(rng: &mut Rng, pages: usize, updates: usize) -> String {
+    let mut order: Vec<u32> = (10..100).collect();
+    rng.shuffle(&mut order);
+    order.truncate(pages.clamp(1, 90));
+
+    let mut rules = Vec::new();
+    for i in 0..order.len() {
+        for j in i + 1..order.len() {
+            rules.push(format!("{}|{}\n", order[i], order[j]));
+        }
+    }
+    rng.shuffle(&mut rules);
+    let mut res = rules.concat();
+    res.push('\n');
+
+    for _ in 0..updates {
+        let max_len = (order.len() - 1) / 2;
+        let len = 2 * rng.range(0, max_len.min(11) as i64) as usize + 1;
+        let mut update: Vec<usize> = (0..order.len()).collect();
+        rng.shuffle(&mut update);
+        update.truncate(len);
+        if rng.chance(0.5) {
+            update.sort_unstable();
+        }
+        let update: Vec<String> = update.iter().map(|&i| order[i].to_string()).collect();
+        res += &update.join(",");
+        res.push('\n');
+    }
+    res
+}
+
+/// A lab with obstacles covering about `density` of it and a guard facing
+/// north.
+pub fn day6(rng: &mut Rng, width: usize, height: usize, density: f64) -> String {
+    let mut grid = Grid::from_fn(width, height, |_| if rng.chance(density) { b'#' } else { b'.' });
+    let guard = (rng.below(height as u64) as usize, rng.below(width as u64) as usize);
+    grid[guard] = b'^';
+    grid.to_string()
+}
+
+/// Calibration equations of 1 to `max_values` numbers. About half of them can
+/// be solved with `+`, `*` and `||`.
+pub fn day7(rng: &mut Rng, equations: usize, max_values: usize) -> String {
+    // Keep the total well within a u64.
+    const LIMIT: u64 = 1_000_000_000_000_000;
+    let mut res = String::new();
+    for _ in 0..equations {
+        let len = rng.range(1, max_values.max(1) as i64);
+        let mut total = rng.range(1, 99) as u64;
+        let mut values = vec![total];
+        for _ in 1..len {
+            let v = if rng.chance(0.8) { rng.range(1, 9) } else { rng.range(10, 999) } as u64;
+            let next = match rng.below(3) {
+                0 => total.checked_add(v),
+                1 => total.checked_mul(v),
+                _ => total.checked_mul(10u64.pow(v.ilog10() + 1)).and_then(|t| t.checked_add(v)),
+            };
+            match next {
+                Some(next) if next < LIMIT => total = next,
+                _ => break,
+            }
+            values.push(v);
+        }
+        if rng.chance(0.5) {
+            total += rng.range(1, 9) as u64;
+        }
+        let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+        res += &format!("{}: {}\n", total, values.join(" "));
+    }
+    res
+}
+
+/// A map with up to `per_frequency` antennas of each of `frequencies`
+/// frequencies (at most 62).
+pub fn day8(rng: &mut Rng, width: usize, height: usize, frequencies: usize, per_frequency: usize) -> String {
+    const NAMES: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    let mut grid = Grid::new(width, height, b'.');
+    for &name in NAMES.iter().take(frequencies) {
+        for _ in 0..per_frequency {
+            let pos = (rng.below(height as u64) as usize, rng.below(width as u64) as usize);
+            if grid[pos] == b'.' {
+                grid[pos] = name;
+            }
+        }
+    }
+    grid.to_string()
+}
+
+/// A disk map of `files` files of 1 to 9 blocks, with gaps of 0 to 9 blocks
+/// between them.
+pub fn day9(rng: &mut Rng, files: usize) -> String {
+    let mut res = String::new();
+    for i in 0..files {
+        if i > 0 {
+            res.push((b'0' + rng.range(0, 9) as u8) as char);
+        }
+        res.push((b'1' + rng.range(0, 8) as u8) as char);
+    }
+    res.push('\n');
+    res
+}
+
+/// A topographic map of hills that fall away from random peaks, with some
+/// noise.
+pub fn day10(rng: &mut Rng, width: usize, height: usize) -> String {
+    let peaks: Vec<Pos> = (0..(width * height / 40).max(1))
+        .map(|_| (rng.below(height as u64) as usize, rng.below(width as u64) as usize))
+        .collect();
+    Grid::from_fn(width, height, |pos| {
+        if rng.chance(0.05) {
+            return b'0' + rng.below(10) as u8;
+        }
+        let dist = peaks.iter().map(|p| p.0.abs_diff(pos.0) + p.1.abs_diff(pos.1)).min().unwrap();
+        b'0' + 9 - dist.min(9) as u8
+    }).to_string()
+}
+
+/// A line of `stones` stones numbered up to `max_value`.
+pub fn day11(rng: &mut Rng, stones: usize, max_value: u64) -> String {
+    let stones: Vec<String> = (0..stones).map(|_| rng.below(max_value + 1).to_string()).collect();
+    stones.join(" ") + "\n"
+}
+
+/// A garden split into about `regions` regions around random seeds. Regions
+/// with the same plant can touch, and merge when they do. Then `singles`
+/// random plots get a plant that none of their neighbors has, making regions
+/// of one plot. A garden with no width or height is empty.
+pub fn day12(rng: &mut Rng, width: usize, height: usize, regions: usize, singles: usize) -> String {
+    let seeds: Vec<(Pos, u8)> = (0..regions.max(1))
+        .map(|_| ((rng.below(height as u64) as usize, rng.below(width as u64) as usize), b'A' + rng.below(26) as u8))
+        .collect();
+    let mut garden = Grid::from_fn(width, height, |pos| {
+        seeds.iter().min_by_key(|(p, _)| p.0.abs_diff(pos.0) + p.1.abs_diff(pos.1)).unwrap().1
+    });
+    if width * height > 0 {
+        for _ in 0..singles {
+            let pos = (rng.below(height as u64) as usize, rng.below(width as u64) as usize);
+            let plant = loop {
+                let plant = b'A' + rng.below(26) as u8;
+                if garden.neighbors4(pos).all(|(_, n)| garden[n] != plant) {
+                    break plant;
+                }
+            };
+            garden[pos] = plant;
+        }
+    }
+    garden.to_string()
+}
+
+/// Claw machines, with a chance of `colinear` that a machine's buttons move
+/// along the same line and otherwise in different directions. About half of
+/// the prizes can be reached, a few of them only with more than 100 presses.
+pub fn day13(rng: &mut Rng, machines: usize, colinear: f64) -> String {
+    let mut res = String::new();
+    for i in 0..machines {
+        // Without colinear machines, this makes the same inputs as before
+        // there could be any.
+        let (a, b) = if colinear > 0.0 && rng.chance(colinear) {
+            let dir = (rng.range(1, 12), rng.range(1, 12));
+            let (ka, kb) = (rng.range(1, 8), rng.range(1, 8));
+            ((dir.0 * ka, dir.1 * ka), (dir.0 * kb, dir.1 * kb))
+        } else {
+            loop {
+                let a = (rng.range(10, 99), rng.range(10, 99));
+                let b = (rng.range(10, 99), rng.range(10, 99));
+                if a.0 * b.1 != a.1 * b.0 {
+                    break (a, b);
+                }
+            }
+        };
+        let prize = if rng.chance(0.5) {
+            let (na, nb) = (rng.range(0, 110), rng.range(0, 110));
+            (na * a.0 + nb * b.0, na * a.1 + nb * b.1)
+        } else {
+            (rng.range(1000, 20000), rng.range(1000, 20000))
+        };
+        if i > 0 {
+            res.push('\n');
+        }
+        res += &format!("Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}\n",
+            a.0, a.1, b.0, b.1, prize.0, prize.1);
+    }
+    res
+}
+
+/// `robots` robots in a `width` by `height` space (at least 10 wide), moving
+/// at less than one lap per second. At some point they all stand in different
+/// places with a line of 10 robots among them, so part 2 has an answer.
+pub fn day14(rng: &mut Rng, robots: usize, width: usize, height: usize) -> String {
+    assert!(width >= 10, "the space must be at least 10 wide");
+    let (w, h) = (width as i64, height as i64);
+    let robots = robots.clamp(10, width * height);
+    let time = rng.range(0, w * h - 1);
+
+    // Where the robots are at `time`, all different.
+    let row = rng.range(0, h - 1);
+    let col = rng.range(0, w - 10);
+    let mut places: Vec<(i64, i64)> = (col..col + 10).map(|x| (x, row)).collect();
+    let mut used: HashSet<(i64, i64)> = places.iter().copied().collect();
+    while places.len() < robots {
+        let place = (rng.range(0, w - 1), rng.range(0, h - 1));
+        if used.insert(place) {
+            places.push(place);
+        }
+    }
+    rng.shuffle(&mut places);
+
+    let mut res = String::new();
+    for (x, y) in places {
+        let (vx, vy) = (rng.range(1 - w, w - 1), rng.range(1 - h, h - 1));
+        // Run time backwards to find where each robot starts.
+        let px = (x - vx * time).rem_euclid(w);
+        let py = (y - vy * time).rem_euclid(h);
+        res += &format!("p={},{} v={},{}\n", px, py, vx, vy);
+    }
+    res
+}
+
+/// A walled-in warehouse with some walls and boxes inside, one robot and
+/// `moves` moves in lines of up to 1000.
+pub fn day15(rng: &mut Rng, width: usize, height: usize, moves: usize) -> String {
+    let (width, height) = (width.max(3), height.max(3));
+    let mut grid = Grid::from_fn(width, height, |(row, col)| {
+        if row == 0 || col == 0 || row == height - 1 || col == width - 1 || rng.chance(0.05) {
+            b'#'
+        } else if rng.chance(0.3) {
+            b'O'
+        } else {
+            b'.'
+        }
+    });
+    let robot = (rng.range(1, height as i64 - 2) as usize, rng.range(1, width as i64 - 2) as usize);
+    grid[robot] = b'@';
+
+    let mut res = grid.to_string();
+    let arrows: Vec<char> = (0..moves).map(|_| *rng.pick(b"^>v<") as char).collect();
+    for line in arrows.chunks(1000) {
+        res.push('\n');
+        res.extend(line);
+    }
+    res.push('\n');
+    res
+}
+
+// A maze with walls between the cells at odd positions, with sides rounded up
+// to odd numbers. Every open cell can be reached from every other. With
+// `loops`, some extra walls are removed so that there are several routes.
+// Returns the maze, and for each cell the direction back towards the bottom
+// left corner.
+fn maze(rng: &mut Rng, width: usize, height: usize, loops: f64) -> (Grid<u8>, Grid<Option<Direction>>) {
+    let (width, height) = ((width.max(5) - 1) | 1, (height.max(5) - 1) | 1);
+    let mut grid = Grid::new(width, height, b'#');
+    let mut parents: Grid<Option<Direction>> = Grid::new(width, height, None);
+    let start = (height - 2, 1);
+    grid[start] = b'.';
+
+    // A randomized depth first search that knocks down the wall to each new
+    // cell.
+    let mut stack = vec![start];
+    while let Some(&pos) = stack.last() {
+        let mut dirs = [Direction::North, Direction::East, Direction::South, Direction::West];
+        rng.shuffle(&mut dirs);
+        let next = dirs.iter().find_map(|&dir| {
+            let wall = grid.step(pos, dir)?;
+            let cell = grid.step(wall, dir)?;
+            (grid[cell] == b'#' && cell.0 < height - 1 && cell.1 < width - 1).then_some((dir, wall, cell))
+        });
+        match next {
+            Some((dir, wall, cell)) => {
+                grid[wall] = b'.';
+                grid[cell] = b'.';
+                parents[wall] = Some(dir.reverse());
+                parents[cell] = Some(dir.reverse());
+                stack.push(cell);
+            },
+            None => {
+                stack.pop();
+            },
+        }
+    }
+
+    for row in 1..height - 1 {
+        for col in 1..width - 1 {
+            // Walls between two cells in a row or a column.
+            if (row % 2 == 1) != (col % 2 == 1) && grid[(row, col)] == b'#' && rng.chance(loops) {
+                grid[(row, col)] = b'.';
+            }
+        }
+    }
+    (grid, parents)
+}
+
+/// A maze about `width` by `height` with several routes from `S` in the
+/// bottom left to `E` in the top right.
+pub fn day16(rng: &mut Rng, width: usize, height: usize) -> String {
+    let (mut grid, _) = maze(rng, width, height, 0.1);
+    let (width, height) = (grid.width(), grid.height());
+    grid[(height - 2, 1)] = b'S';
+    grid[(1, width - 2)] = b'E';
+    grid.to_string()
+}
+
+/// A 3-bit computer with a program shaped like the real ones, which can output
+/// itself for some value of register A.
+pub fn day17(rng: &mut Rng) -> String {
+    loop {
+        let a = rng.range(1 << 45, (1 << 48) - 1);
+        let mut middle = [[1, rng.range(0, 7)], [4, rng.range(0, 7)]];
+        rng.shuffle(&mut middle);
+        let mut tail = [[0, 3], [5, 5]];
+        rng.shuffle(&mut tail);
+        let mut program = vec![2, 4, 1, rng.range(0, 7), 7, 5];
+        program.extend(middle.concat());
+        program.extend(tail.concat());
+        program.extend([3, 0]);
+
+        let program: Vec<String> = program.iter().map(|i| i.to_string()).collect();
+        let input = format!("Register A: {}\nRegister B: 0\nRegister C: 0\n\nProgram: {}\n", a, program.join(","));
+        if crate::y2024::day17::try_part2(&input).is_ok() {
+            return input;
+        }
+    }
+}
+
+/// Every position in a `dim` by `dim` space except the corners, in an order
+/// that leaves a way out after the first `count` bytes but blocks it
+/// eventually.
+pub fn day18(rng: &mut Rng, dim: usize, count: usize) -> String {
+    // A random staircase from the start to the exit, which stays open until the
+    // first `count` bytes have fallen.
+    let mut path = HashSet::new();
+    let mut pos = (0, 0);
+    while pos != (dim - 1, dim - 1) {
+        let down = pos.1 == dim - 1 || (pos.0 < dim - 1 && rng.chance(0.5));
+        pos = if down { (pos.0 + 1, pos.1) } else { (pos.0, pos.1 + 1) };
+        path.insert(pos);
+    }
+    let mut first: Vec<Pos> = Vec::new();
+    let mut rest: Vec<Pos> = Vec::new();
+    for y in 0..dim {
+        for x in 0..dim {
+            if path.contains(&(y, x)) || (y, x) == (0, 0) {
+                rest.push((x, y));
+            } else {
+                first.push((x, y));
+            }
+        }
+    }
+    rest.retain(|&p| p != (0, 0) && p != (dim - 1, dim - 1));
+    rng.shuffle(&mut first);
+    rest.extend(first.drain(count.min(first.len())..));
+    rng.shuffle(&mut rest);
+
+    first.iter().chain(&rest).map(|(x, y)| format!("{},{}\n", x, y)).collect()
+}
+
+/// `patterns` distinct towel patterns of 1 to `max_len` stripes, and designs
+/// that are built from them about half of the time.
+pub fn day19(rng: &mut Rng, patterns: usize, max_len: usize, designs: usize) -> String {
+    let max_len = max_len.max(1);
+    let stripes = |rng: &mut Rng, len: usize| -> String {
+        (0..len).map(|_| *rng.pick(b"wubrg") as char).collect()
+    };
+    // There are only so many short patterns, so this might find fewer. One
+    // color never appears on its own, so that some designs are impossible.
+    let rare = (*rng.pick(b"wubrg") as char).to_string();
+    let mut set = HashSet::new();
+    let mut towels = Vec::new();
+    for _ in 0..patterns * 4 {
+        let len = rng.range(1, max_len as i64) as usize;
+        let towel = stripes(rng, len);
+        if towels.len() < patterns && towel != rare && set.insert(towel.clone()) {
+            towels.push(towel);
+        }
+    }
+
+    let mut res = towels.join(", ") + "\n\n";
+    for _ in 0..designs {
+        let len = rng.range(20, 60) as usize;
+        let mut design = String::new();
+        if rng.chance(0.5) {
+            while design.len() < len {
+                let towel: &String = rng.pick(&towels);
+                design += towel;
+            }
+        } else {
+            design = stripes(rng, len);
+        }
+        res += &design;
+        res.push('\n');
+    }
+    res
+}
+
+/// A racetrack about `width` by `height` with a single winding path from `S`
+/// to `E`.
+pub fn day20(rng: &mut Rng, width: usize, height: usize) -> String {
+    let (maze, parents) = maze(rng, width, height, 0.0);
+    let (width, height) = (maze.width(), maze.height());
+    let mut grid = Grid::new(width, height, b'#');
+    // The path through the maze from E back to S.
+    let mut pos = (1, width - 2);
+    grid[pos] = b'E';
+    while let Some(dir) = parents[pos] {
+        pos = dir.advance(pos);
+        grid[pos] = b'.';
+    }
+    grid[pos] = b'S';
+    grid.to_string()
+}
+
+/// `codes` door codes of three digits and an `A`.
+pub fn day21(rng: &mut Rng, codes: usize) -> String {
+    (0..codes).map(|_| format!("{:03}A\n", rng.below(1000))).collect()
+}
+
+/// Initial secret numbers for `buyers` buyers.
+pub fn day22(rng: &mut Rng, buyers: usize) -> String {
+    (0..buyers).map(|_| format!("{}\n", rng.range(1, (1 << 24) - 1))).collect()
+}
+
+/// A network of `computers` computers (at most 676) with about `degree` links
+/// each, and a LAN party of `clique` computers (at least 3) that are all linked
+/// to each other.
+pub fn day23(rng: &mut Rng, computers: usize, degree: usize, clique: usize) -> String {
+    let mut names: Vec<String> = (b'a'..=b'z')
+        .flat_map(|a| (b'a'..=b'z').map(move |b| String::from_utf8(vec![a, b]).unwrap()))
+        .collect();
+    rng.shuffle(&mut names);
+    names.truncate(computers.clamp(3, 676));
+    let clique = clique.clamp(3, names.len());
+
+    let mut links: HashSet<(usize, usize)> = HashSet::new();
+    for a in 0..clique {
+        for b in a + 1..clique {
+            links.insert((a, b));
+        }
+    }
+    for a in 0..names.len() {
+        for _ in 0..degree / 2 {
+            let b = rng.below(names.len() as u64) as usize;
+            if a != b {
+                links.insert((a.min(b), a.max(b)));
+            }
+        }
+    }
+
+    let mut links: Vec<(usize, usize)> = links.into_iter().collect();
+    links.sort_unstable();
+    rng.shuffle(&mut links);
+    links.iter().map(|&(a, b)| format!("{}-{}\n", names[a], names[b])).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn test_rng() {
+        let (mut rng, mut other) = (Rng::new(7), Rng::new(7));
+        assert!((0..100).all(|_| rng.next_u64() == other.next_u64()));
+        assert!((0..1000).all(|_| (3..=5).contains(&rng.range(3, 5))));
+        let mut items: Vec<u32> = (0..20).collect();
+        rng.shuffle(&mut items);
+        items.sort_unstable();
+        assert_eq!((0..20).collect::<Vec<u32>>(), items);
+        assert_eq!(None, generate(26, 0, 1.0));
+    }
+
+    #[test]
+    fn test_generate() {
+        // Small inputs, to keep this quick in debug builds. Every solver must
+        // accept them.
+        for day in 1..=23 {
+            for seed in 0..2 {
+                let input = generate(day, seed, 0.05).unwrap();
+                assert_eq!(Some(&input), generate(day, seed, 0.05).as_ref());
+                for solver in registry::solvers().iter().filter(|s| s.day == day && s.is_default()) {
+                    // Day 14's space can't be made smaller, and searching it
+                    // is slow in debug builds.
+                    if day == 14 && solver.part == 2 {
+                        assert!(solver.parse.is_none_or(|parse| parse(&input).is_ok()));
+                        continue;
+                    }
+                    if let Err(e) = solver.try_solve(&input) {
+                        panic!("{:?} with seed {}: {}", solver, seed, e);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_small() {
+        // Smaller spaces than the solvers use, for the parts that take sizes.
+        let input = day14(&mut Rng::new(1), 12, 11, 7);
+        assert_eq!(12, input.lines().count());
+        assert!(crate::y2024::day14::parse_robots(&input, 11, 7).is_ok());
+
+        let input = day18(&mut Rng::new(1), 7, 12);
+        assert_eq!(47, input.lines().count());
+
+        let input = day20(&mut Rng::new(1), 15, 15);
+        assert_eq!(1, input.matches('S').count());
+        assert_eq!(1, input.matches('E').count());
+        assert!(crate::y2024::day20::try_part1(&input).is_ok());
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        use crate::y2024::{day12, day13, day19};
+
+        // Empty gardens, and regions of a single plot.
+        assert_eq!("", day12(&mut Rng::new(1), 0, 0, 5, 3));
+        assert_eq!((Ok(0), Ok(0)), (day12::try_part1(""), day12::try_part2("")));
+        let input = day12(&mut Rng::new(1), 1, 1, 1, 1);
+        assert_eq!((Ok(4), Ok(4)), (day12::try_part1(&input), day12::try_part2(&input)));
+        let input = day12(&mut Rng::new(1), 20, 15, 6, 30);
+        assert!(day12::parse(&input).unwrap().regions.iter().any(|region| region.len() == 1));
+        assert!(day12::try_part1(&input).is_ok() && day12::try_part2(&input).is_ok());
+
+        // Buttons that move along the same line.
+        for seed in 0..20 {
+            let input = day13(&mut Rng::new(seed), 10, 1.0);
+            assert_eq!(day13::reference_total(&input, false), day13::try_part1(&input).ok());
+            assert_eq!(day13::reference_total(&input, true), day13::try_part2(&input).ok());
+            assert_eq!(day13::try_part2(&input), day13::stream_part2(input.as_bytes()));
+        }
+
+        // Towels too long to pack into a u64, checked against counting the
+        // ways to make each design directly.
+        let input = day19(&mut Rng::new(1), 60, 14, 30);
+        let (towels, designs) = input.split_once("\n\n").unwrap();
+        let towels: Vec<&str> = towels.split(", ").collect();
+        assert!(towels.iter().any(|towel| towel.len() > 8));
+        let ways: u64 = designs.lines().map(|design| {
+            let mut ways = vec![0u64; design.len() + 1];
+            ways[0] = 1;
+            for i in 0..design.len() {
+                for towel in towels.iter().filter(|towel| design[i..].starts_with(**towel)) {
+                    ways[i + towel.len()] += ways[i];
+                }
+            }
+            ways[design.len()]
+        }).sum();
+        assert_eq!(Ok(ways), day19::try_part2(&input));
+        assert_eq!(Ok(ways), day19::stream_part2(input.as_bytes()));
+    }
+}
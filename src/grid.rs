@@ -0,0 +1,391 @@
+// Shared 2D grid helpers for the puzzles that operate on a character map.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use crate::error::{lines, Error, Result};
+
+/// A `(row, col)` coordinate into a `Grid`.
+pub type Pos = (usize, usize);
+
+/// Offsets to all 8 neighbors of a cell, clockwise starting from North.
+pub const DELTAS8: [(isize, isize); 8] = [
+    (-1,  0), (-1,  1), ( 0,  1), ( 1,  1),
+    ( 1,  0), ( 1, -1), ( 0, -1), (-1, -1),
+];
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// All 4 directions, clockwise starting from North.
+    pub const ALL: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+
+    /// Parses one of `^`, `>`, `v` or `<`.
+    pub fn from_arrow(b: u8) -> Option<Self> {
+        match b {
+            b'^' => Some(Direction::North),
+            b'>' => Some(Direction::East),
+            b'v' => Some(Direction::South),
+            b'<' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    pub fn to_arrow(&self) -> u8 {
+        match self {
+            Direction::North => b'^',
+            Direction::East  => b'>',
+            Direction::South => b'v',
+            Direction::West  => b'<',
+        }
+    }
+
+    /// The `(row, col)` offset of a single step in this direction.
+    pub fn delta(&self) -> (isize, isize) {
+        match self {
+            Direction::North => (-1,  0),
+            Direction::East  => ( 0,  1),
+            Direction::South => ( 1,  0),
+            Direction::West  => ( 0, -1),
+        }
+    }
+
+    /// Steps one cell in this direction without any bounds checks. Callers
+    /// are expected to know that the map is surrounded by walls. Use
+    /// `Grid::step` otherwise.
+    pub fn advance(&self, pos: Pos) -> Pos {
+        let (dr, dc) = self.delta();
+        (pos.0.wrapping_add_signed(dr), pos.1.wrapping_add_signed(dc))
+    }
+
+    /// The inverse of `advance`.
+    pub fn backup(&self, pos: Pos) -> Pos {
+        self.reverse().advance(pos)
+    }
+
+    pub fn turn_left(&self) -> Direction {
+        match self {
+            Direction::North => Direction::West,
+            Direction::East  => Direction::North,
+            Direction::South => Direction::East,
+            Direction::West  => Direction::South,
+        }
+    }
+
+    pub fn turn_right(&self) -> Direction {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East  => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West  => Direction::North,
+        }
+    }
+
+    pub fn reverse(&self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East  => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West  => Direction::East,
+        }
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        match self {
+            Direction::North | Direction::South => true,
+            Direction::East | Direction::West => false,
+        }
+    }
+}
+
+/// A cell type that maps to a single byte of puzzle input. This is used to
+/// parse and print grids.
+pub trait GridCell: Sized {
+    fn from_byte(b: u8) -> Self;
+    fn to_byte(&self) -> u8;
+}
+
+impl GridCell for u8 {
+    fn from_byte(b: u8) -> Self {
+        b
+    }
+    fn to_byte(&self) -> u8 {
+        *self
+    }
+}
+
+/// `#` is true, anything else is false.
+impl GridCell for bool {
+    fn from_byte(b: u8) -> Self {
+        b == b'#'
+    }
+    fn to_byte(&self) -> u8 {
+        if *self { b'#' } else { b'.' }
+    }
+}
+
+/// A dense, row-major rectangular grid.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        let mut cells = Vec::with_capacity(width * height);
+        for row in 0..height {
+            for col in 0..width {
+                cells.push(f((row, col)));
+            }
+        }
+        Grid { cells, width, height }
+    }
+
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+        where T: Clone
+    {
+        Grid { cells: vec![fill; width * height], width, height }
+    }
+
+    /// Builds a grid from the leading block of lines in `input`. Parsing stops
+    /// at the first empty line so that puzzles with a map followed by other
+    /// data can parse just the map. Every row must have the same width.
+    pub fn parse_with(input: &str, mut f: impl FnMut(Pos, u8) -> T) -> Self {
+        Self::try_parse_with(0, input, |pos, b| Ok(f(pos, b))).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// The fallible version of `parse_with`. If `f` can't convert a byte, it
+    /// returns a description of what it expected instead.
+    pub fn try_parse_with(day: u32, input: &str,
+            mut f: impl FnMut(Pos, u8) -> std::result::Result<T, &'static str>) -> Result<Self> {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = 0;
+        let mut height = 0;
+        for line in lines(day, input) {
+            let text = line.text.trim_ascii_end();
+            if text.is_empty() {
+                break;
+            }
+            if height == 0 {
+                width = text.len();
+            }
+            if text.len() != width {
+                return Err(Error::new(day, line.number, 0,
+                    format!("a row of width {}", width), format!("a row of width {}", text.len())));
+            }
+            for (col, b) in text.bytes().enumerate() {
+                let cell = f((height, col), b).map_err(|expected| {
+                    Error::new(day, line.number, col + 1, expected, format!("`{}`", b.escape_ascii()))
+                })?;
+                cells.push(cell);
+            }
+            height += 1;
+        }
+        Ok(Grid { cells, width, height })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.0 < self.height && pos.1 < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.cells[self.index_of(pos)])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            let i = self.index_of(pos);
+            Some(&mut self.cells[i])
+        } else {
+            None
+        }
+    }
+
+    /// Converts a coordinate into an offset into the row-major cell storage.
+    pub fn index_of(&self, pos: Pos) -> usize {
+        debug_assert!(self.contains(pos));
+        pos.0 * self.width + pos.1
+    }
+
+    /// The inverse of `index_of`.
+    pub fn pos_of(&self, index: usize) -> Pos {
+        debug_assert!(index < self.cells.len());
+        (index / self.width, index % self.width)
+    }
+
+    /// Applies a signed `(row, col)` offset to `pos`, returning `None` if the
+    /// result is outside the grid.
+    pub fn offset(&self, pos: Pos, delta: (isize, isize)) -> Option<Pos> {
+        let row = pos.0.checked_add_signed(delta.0)?;
+        let col = pos.1.checked_add_signed(delta.1)?;
+        if self.contains((row, col)) {
+            Some((row, col))
+        } else {
+            None
+        }
+    }
+
+    /// A bounds-checked `Direction::advance`.
+    pub fn step(&self, pos: Pos, dir: Direction) -> Option<Pos> {
+        self.offset(pos, dir.delta())
+    }
+
+    /// The in-bounds orthogonal neighbors of `pos`.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = (Direction, Pos)> + '_ {
+        Direction::ALL.into_iter().filter_map(move |dir| {
+            self.step(pos, dir).map(|p| (dir, p))
+        })
+    }
+
+    /// The in-bounds orthogonal and diagonal neighbors of `pos`.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DELTAS8.into_iter().filter_map(move |delta| self.offset(pos, delta))
+    }
+
+    /// All coordinates in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i / width, i % width))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks() panics on a zero chunk size, which an empty grid would have.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The first coordinate (in row-major order) whose cell matches `pred`.
+    pub fn position(&self, pred: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.cells.iter().position(pred).map(|i| self.pos_of(i))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T: GridCell> From<&str> for Grid<T> {
+    fn from(input: &str) -> Self {
+        Grid::parse_with(input, |_, b| T::from_byte(b))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        assert!(self.contains(pos), "{:?} is outside of the grid", pos);
+        &self.cells[pos.0 * self.width + pos.1]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        assert!(self.contains(pos), "{:?} is outside of the grid", pos);
+        &mut self.cells[pos.0 * self.width + pos.1]
+    }
+}
+
+impl<T: GridCell> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell.to_byte() as char)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEST_INPUT: &str = "\
+        #..\n\
+        .@#\n\
+        \n\
+        ignored";
+
+    #[test]
+    fn test_parse() {
+        let grid: Grid<u8> = Grid::from(TEST_INPUT);
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(b'@', grid[(1, 1)]);
+        assert_eq!(Some((1, 1)), grid.position(|b| *b == b'@'));
+        assert_eq!("#..\n.@#\n", grid.to_string());
+
+        let walls: Grid<bool> = Grid::from(TEST_INPUT);
+        assert_eq!(2, walls.iter().filter(|(_, w)| **w).count());
+        assert_eq!("#..\n..#\n", walls.to_string());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Grid::try_parse_with(6, "..\n.", |_, b| Ok(b)).unwrap_err();
+        assert_eq!("day 6, line 2: expected a row of width 2, found a row of width 1", err.to_string());
+        let err = Grid::try_parse_with(6, "..\n.x", |_, b| {
+            if b == b'.' { Ok(b) } else { Err("`.`") }
+        }).unwrap_err();
+        assert_eq!("day 6, line 2, column 2: expected `.`, found `x`", err.to_string());
+    }
+
+    #[test]
+    fn test_coordinates() {
+        let grid: Grid<u8> = Grid::from(TEST_INPUT);
+        for (i, pos) in grid.positions().enumerate() {
+            assert_eq!(i, grid.index_of(pos));
+            assert_eq!(pos, grid.pos_of(i));
+        }
+        assert_eq!(None, grid.get((2, 0)));
+        assert_eq!(None, grid.offset((0, 0), (0, -1)));
+        assert_eq!(Some((1, 2)), grid.step((0, 2), Direction::South));
+        assert_eq!(None, grid.step((0, 2), Direction::East));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid: Grid<u8> = Grid::from(TEST_INPUT);
+        let n4: Vec<(Direction, Pos)> = grid.neighbors4((0, 0)).collect();
+        assert_eq!(vec![(Direction::East, (0, 1)), (Direction::South, (1, 0))], n4);
+        assert_eq!(3, grid.neighbors8((0, 0)).count());
+        assert_eq!(5, grid.neighbors8((0, 1)).count());
+    }
+
+    #[test]
+    fn test_direction() {
+        for dir in Direction::ALL {
+            assert_eq!(dir, dir.turn_left().turn_right());
+            assert_eq!(dir.reverse(), dir.turn_right().turn_right());
+            assert_eq!((5, 5), dir.backup(dir.advance((5, 5))));
+            assert_eq!(Some(dir), Direction::from_arrow(dir.to_arrow()));
+        }
+    }
+}
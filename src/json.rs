@@ -0,0 +1,30 @@
+// Writing JSON by hand for the command line's `--json` output and the tracer,
+// without a serializer.
+
+use std::fmt::Write;
+
+/// `s` as a quoted JSON string.
+pub(crate) fn string(s: &str) -> String {
+    let mut res = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            c if (c as u32) < 0x20 => write!(res, "\\u{:04x}", c as u32).unwrap(),
+            c => res.push(c),
+        }
+    }
+    res.push('"');
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string() {
+        assert_eq!(r#""a\"b\\c\n\u0001""#, string("a\"b\\c\n\u{1}"));
+    }
+}
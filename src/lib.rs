@@ -3,28 +3,34 @@ extern crate aoc_runner;
 #[macro_use]
 extern crate aoc_runner_derive;
 
-pub mod day1;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
-pub mod day9;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-pub mod day14;
-pub mod day15;
-pub mod day16;
-pub mod day17;
-pub mod day18;
-pub mod day19;
-pub mod day20;
-pub mod day21;
-pub mod day22;
-pub mod day23;
+pub mod allocs;
+pub mod answer;
+pub mod bench;
+pub mod cli;
+pub mod diff;
+pub mod error;
+pub mod examples;
+pub mod gen;
+pub mod grid;
+pub mod manifest;
+pub mod parallel;
+pub mod registry;
+pub mod scaffold;
+pub mod search;
+pub mod stream;
+pub mod trace;
+pub mod viz;
+mod json;
+mod unchecked;
 
+// Each year's puzzles are in their own module. The 2024 days are also
+// re-exported at the top level, where they were before there were other years.
+pub mod y2024;
+pub use y2024::*;
+
+pub use answer::Answer;
+pub use error::Error;
+pub use registry::{solvers, Solver};
+
+// cargo-aoc only runs one year. The aoc24 binary runs all of them.
 aoc_lib!{ year = 2024 }
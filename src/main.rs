@@ -1,7 +1,5 @@
 extern crate aoc24;
-extern crate aoc_runner_derive;
-extern crate aoc_runner;
 
-use aoc_runner_derive::aoc_main;
-
-aoc_main! { lib = aoc24 }
+fn main() -> std::process::ExitCode {
+    aoc24::cli::main(std::env::args().skip(1))
+}
@@ -0,0 +1,194 @@
+// The expected answers for each puzzle input, kept in answers/<year>.toml so
+// that checking another account's inputs doesn't mean editing source.
+//
+// Only the small subset of TOML that the manifest needs is supported: tables
+// named after inputs, `partN = <integer or string>` keys and `#` comments.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use crate::answer::Answer;
+
+#[derive(Clone, Default, Eq, PartialEq, Debug)]
+pub struct Manifest {
+    // Keyed by input name (e.g. "day7" or "day7-alice") and part.
+    answers: BTreeMap<(String, u32), Answer>,
+}
+
+// Parses a quoted string, returning it and the rest of the line.
+fn parse_string(s: &str) -> Option<(String, &str)> {
+    let mut res = String::new();
+    let mut chars = s.strip_prefix('"')?.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((res, &s[i + 2..])),
+            '\\' => match chars.next()?.1 {
+                'n' => res.push('\n'),
+                't' => res.push('\t'),
+                c @ ('"' | '\\') => res.push(c),
+                _ => return None,
+            },
+            c => res.push(c),
+        }
+    }
+    None
+}
+
+pub(crate) fn parse_value(s: &str) -> Option<Answer> {
+    let (answer, rest) = if s.starts_with('"') {
+        let (text, rest) = parse_string(s)?;
+        (Answer::Text(text), rest)
+    } else {
+        let end = s.find('#').unwrap_or(s.len());
+        let n: i128 = s[..end].trim().replace('_', "").parse().ok()?;
+        (Answer::Int(n), &s[end..])
+    };
+    let rest = rest.trim_start();
+    (rest.is_empty() || rest.starts_with('#')).then_some(answer)
+}
+
+/// The day that an input belongs to, from its name ("day7" or "day7-alice").
+pub fn input_day(name: &str) -> Option<u32> {
+    let rest = name.strip_prefix("day")?;
+    let digits = rest.find('-').map_or(rest, |i| &rest[..i]);
+    digits.parse().ok()
+}
+
+/// The name of an input file: its file name without the `.txt`.
+pub fn input_name(path: &Path) -> String {
+    path.file_stem().map_or_else(String::new, |s| s.to_string_lossy().into_owned())
+}
+
+/// Where the inputs for a year are kept, relative to the root of the repo.
+pub fn input_dir(year: u32) -> PathBuf {
+    PathBuf::from(format!("input/{}", year))
+}
+
+/// Where the manifest for a year is kept, relative to the root of the repo.
+pub fn default_path(year: u32) -> PathBuf {
+    PathBuf::from(format!("answers/{}.toml", year))
+}
+
+/// Every input for `day` in `dir`: `dayN.txt` and any `dayN-<name>.txt`,
+/// sorted by name.
+pub fn find_inputs(dir: &Path, day: u32) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|e| e == "txt"))
+        .filter(|p| input_day(&input_name(p)) == Some(day))
+        .collect();
+    paths.sort();
+    paths
+}
+
+impl Manifest {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut manifest = Manifest::default();
+        let mut table: Option<String> = None;
+        for (i, line) in text.lines().enumerate() {
+            let error = |what: &str| format!("line {}: {}", i + 1, what);
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(rest) = line.strip_prefix('[') {
+                let (name, _) = rest.split_once(']').ok_or_else(|| error("expected `]`"))?;
+                let name = name.trim().trim_matches('"');
+                if input_day(name).is_none() {
+                    return Err(error("expected a table named after an input, like `[day7]`"));
+                }
+                table = Some(name.to_string());
+                continue;
+            }
+            let table = table.as_ref().ok_or_else(|| error("expected a table before any answers"))?;
+            let (key, value) = line.split_once('=').ok_or_else(|| error("expected `partN = <answer>`"))?;
+            let part: u32 = match key.trim() {
+                "part1" => 1,
+                "part2" => 2,
+                _ => return Err(error("expected `part1` or `part2`")),
+            };
+            let answer = parse_value(value.trim()).ok_or_else(|| error("expected an integer or a quoted string"))?;
+            if manifest.answers.insert((table.clone(), part), answer).is_some() {
+                return Err(error("duplicate answer"));
+            }
+        }
+        Ok(manifest)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("can't read {}: {}", path.display(), e))?;
+        Manifest::parse(&text).map_err(|e| format!("{}, {}", path.display(), e))
+    }
+
+    /// The expected answer for one part of an input, if it is known.
+    pub fn get(&self, input: &str, part: u32) -> Option<&Answer> {
+        self.answers.get(&(input.to_string(), part))
+    }
+
+    /// Every (input name, part) that has an expected answer.
+    pub fn keys(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.answers.keys().map(|(name, part)| (name.as_str(), *part))
+    }
+}
+
+/// The expected answer for the checked-in input of any year's puzzle, or None
+/// if the manifest doesn't have it yet.
+#[cfg(test)]
+pub(crate) fn expected_in(year: u32, day: u32, part: u32) -> Option<Answer> {
+    let manifest = Manifest::load(&default_path(year)).unwrap();
+    manifest.get(&format!("day{}", day), part).cloned()
+}
+
+/// The expected answer for the checked-in input of a 2024 puzzle. Panics if it
+/// isn't in the manifest.
+#[cfg(test)]
+pub(crate) fn expected(day: u32, part: u32) -> Answer {
+    expected_in(2024, day, part).unwrap_or_else(|| panic!("no answer for day {} part {}", day, part))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let manifest = Manifest::parse("\
+            # A comment\n\
+            [day7]\n\
+            part1 = 1_000 # The answer\n\
+            \n\
+            [day7-alice]\n\
+            part2 = \"a,\\\"b\\\" # c\"\n").unwrap();
+        assert_eq!(Some(&Answer::Int(1000)), manifest.get("day7", 1));
+        assert_eq!(Some(&Answer::from("a,\"b\" # c")), manifest.get("day7-alice", 2));
+        assert_eq!(None, manifest.get("day7", 2));
+        assert_eq!(vec![("day7", 1), ("day7-alice", 2)], manifest.keys().collect::<Vec<_>>());
+
+        assert_eq!(Err("line 1: expected a table before any answers".to_string()), Manifest::parse("part1 = 1"));
+        assert_eq!(Err("line 2: expected an integer or a quoted string".to_string()), Manifest::parse("[day1]\npart1 = 1 2"));
+        assert_eq!(Err("line 3: duplicate answer".to_string()), Manifest::parse("[day1]\npart1 = 1\npart1 = 2"));
+        assert_eq!(Err("line 1: expected a table named after an input, like `[day7]`".to_string()), Manifest::parse("[x]"));
+    }
+
+    #[test]
+    fn test_inputs() {
+        assert_eq!(Some(7), input_day("day7"));
+        assert_eq!(Some(17), input_day("day17-alice"));
+        assert_eq!(None, input_day("input7"));
+        assert_eq!("day7-alice", input_name(Path::new("input/2024/day7-alice.txt")));
+
+        assert_eq!(vec![PathBuf::from("input/2024/day1.txt")], find_inputs(&input_dir(2024), 1));
+
+        // Every checked-in input has both answers.
+        let manifest = Manifest::load(&default_path(2024)).unwrap();
+        assert_eq!(46, manifest.keys().count());
+        for day in 1..=23 {
+            for path in find_inputs(Path::new("input/2024"), day) {
+                assert!(manifest.get(&input_name(&path), 1).is_some());
+                assert!(manifest.get(&input_name(&path), 2).is_some());
+            }
+        }
+    }
+}
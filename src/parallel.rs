@@ -0,0 +1,88 @@
+// How many threads the parallel solvers (days 6, 19, 20 and 22) use. By
+// default they share rayon's global pool, which has a thread per core. Like
+// the tracer, this is set globally rather than passed to each solver.
+
+use std::sync::{Arc, RwLock};
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum Threads {
+    /// Rayon's global pool. Its size can be set with `RAYON_NUM_THREADS`.
+    #[default]
+    Default,
+    /// Everything runs on one thread, for measuring single-core performance.
+    Serial,
+    /// A pool of this many threads.
+    Count(usize),
+}
+
+impl Threads {
+    /// Parses a thread count, where 1 means serial.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.parse().ok()? {
+            0 => None,
+            1 => Some(Threads::Serial),
+            n => Some(Threads::Count(n)),
+        }
+    }
+}
+
+static THREADS: RwLock<(Threads, Option<Arc<ThreadPool>>)> = RwLock::new((Threads::Default, None));
+
+/// Sets how many threads the solvers use from now on.
+pub fn set_threads(threads: Threads) -> Result<(), String> {
+    let pool = match threads {
+        Threads::Default => None,
+        Threads::Serial => Some(1),
+        Threads::Count(n) => Some(n),
+    }.map(|n| ThreadPoolBuilder::new()
+        .num_threads(n)
+        .thread_name(|i| format!("aoc24-{}", i))
+        .build()
+        .map(Arc::new)
+        .map_err(|e| format!("can't start {} threads: {}", n, e)))
+    .transpose()?;
+    *THREADS.write().unwrap() = (threads, pool);
+    Ok(())
+}
+
+pub fn threads() -> Threads {
+    THREADS.read().unwrap().0
+}
+
+/// Runs `f`, whose parallel iterators then use the configured pool.
+pub fn install<T: Send>(f: impl FnOnce() -> T + Send) -> T {
+    // The lock isn't held while `f` runs, since the pool can be changed by
+    // another thread meanwhile.
+    let pool = THREADS.read().unwrap().1.clone();
+    match pool {
+        Some(pool) => pool.install(f),
+        None => f(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_threads() {
+        assert_eq!(Some(Threads::Serial), Threads::parse("1"));
+        assert_eq!(Some(Threads::Count(3)), Threads::parse("3"));
+        assert_eq!(None, Threads::parse("0"));
+
+        // Other tests may run in the pool while this one has it set, which
+        // only slows them down.
+        let input = include_str!("../input/2024/day22.txt");
+        let expected = crate::y2024::day22::part1(input);
+        for threads in [Threads::Serial, Threads::Count(3)] {
+            set_threads(threads).unwrap();
+            assert_eq!(threads, super::threads());
+            let n = if threads == Threads::Serial { 1 } else { 3 };
+            assert_eq!(n, install(rayon::current_num_threads));
+            assert_eq!(expected, crate::y2024::day22::part1(input));
+        }
+        set_threads(Threads::Default).unwrap();
+        assert_eq!(rayon::current_num_threads(), install(rayon::current_num_threads));
+    }
+}
@@ -0,0 +1,397 @@
+// A list of every solver that can be looked up and called at runtime, without
+// going through the aoc-runner macros.
+
+use std::collections::BTreeMap;
+use std::io::BufRead;
+use std::time::{Duration, Instant};
+use crate::allocs;
+use crate::answer::Answer;
+use crate::error;
+
+/// One implementation of one part of a puzzle.
+#[derive(Copy, Clone)]
+pub struct Solver {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    /// `DEFAULT` for the main implementation, otherwise the name of an
+    /// alternate.
+    pub name: &'static str,
+    pub run: fn(&str) -> Answer,
+    /// A version that reports bad input instead of panicking. Only the main
+    /// implementations have one.
+    pub try_run: Option<fn(&str) -> error::Result<Answer>>,
+    /// Just the parsing step. Both parts share the same parser, and the
+    /// alternates don't have one.
+    pub parse: Option<fn(&str) -> error::Result<()>>,
+    /// A version that reads the input a line or a chunk at a time, for inputs
+    /// too big to hold in memory.
+    pub stream: Option<fn(&mut dyn BufRead) -> error::Result<Answer>>,
+    /// The names of the parameters that `with_params` takes, if any.
+    pub params: &'static [&'static str],
+    /// A version that takes parameters, like a smaller grid for an example.
+    /// Only the main implementations of some days have one.
+    pub with_params: Option<fn(&str, &Params) -> error::Result<Answer>>,
+}
+
+pub const DEFAULT: &str = "default";
+
+impl Solver {
+    pub fn is_default(&self) -> bool {
+        self.name == DEFAULT
+    }
+
+    /// Runs `try_run` if there is one, otherwise `run`.
+    pub fn try_solve(&self, input: &str) -> error::Result<Answer> {
+        match self.try_run {
+            Some(try_run) => try_run(input),
+            None => Ok((self.run)(input)),
+        }
+    }
+
+    /// Runs `with_params`, or `try_solve` if there are no parameters to pass.
+    /// Parameters that this solver doesn't take are reported as an error.
+    pub fn solve_with(&self, input: &str, params: &Params) -> error::Result<Answer> {
+        if params.is_empty() {
+            return self.try_solve(input);
+        }
+        if let Some(name) = params.names().find(|name| !self.params.contains(name)) {
+            let expected = match self.params {
+                [] => "no parameters".to_string(),
+                names => format!("one of {}", names.iter().map(|n| format!("`{}`", n)).collect::<Vec<_>>().join(", ")),
+            };
+            return Err(error::Error::new(self.day, 0, 0, expected, format!("`{}`", name)));
+        }
+        // A solver with parameter names always has with_params.
+        (self.with_params.unwrap())(input, params)
+    }
+}
+
+impl std::fmt::Debug for Solver {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} day {} part {} ({})", self.year, self.day, self.part, self.name)
+    }
+}
+
+/// Named integer parameters for a solver, which override values that are
+/// fixed for the real input.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Params(BTreeMap<String, i64>);
+
+impl Params {
+    pub fn new() -> Self {
+        Params::default()
+    }
+
+    pub fn set(&mut self, name: &str, value: i64) {
+        self.0.insert(name.to_string(), value);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+
+    /// The value of `name`, or `default` if it isn't set. A value that doesn't
+    /// fit in a `T` is an error for `day`.
+    pub fn get<T: TryFrom<i64>>(&self, day: u32, name: &str, default: T) -> error::Result<T> {
+        match self.0.get(name) {
+            Some(&value) => T::try_from(value).map_err(|_| {
+                error::Error::new(day, 0, 0, format!("a `{}` in range", name), value.to_string())
+            }),
+            None => Ok(default),
+        }
+    }
+}
+
+/// What a day's solve_part1() and solve_part2() return: an answer, or a
+/// Result for parts that can fail after parsing.
+pub trait Solution {
+    fn into_answer(self) -> error::Result<Answer>;
+}
+
+impl<T: Into<Answer>> Solution for T {
+    fn into_answer(self) -> error::Result<Answer> {
+        Ok(self.into())
+    }
+}
+
+impl<T: Into<Answer>> Solution for error::Result<T> {
+    fn into_answer(self) -> error::Result<Answer> {
+        self.map(Into::into)
+    }
+}
+
+/// Several parts of one day, solved from a single parse of the input.
+#[derive(Clone, Debug)]
+pub struct Solved {
+    pub parse_time: Duration,
+    /// What parsing allocated, with the `count-allocs` feature.
+    pub parse_allocs: Option<allocs::Stats>,
+    /// Each part asked for, in the same order.
+    pub parts: Vec<SolvedPart>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SolvedPart {
+    pub part: u32,
+    pub answer: error::Result<Answer>,
+    pub time: Duration,
+    pub allocs: Option<allocs::Stats>,
+}
+
+// A day's parse() followed by its solve_part1() and solve_part2().
+#[derive(Copy, Clone)]
+struct Day {
+    year: u32,
+    day: u32,
+    solve: fn(&str, &[u32]) -> error::Result<Solved>,
+}
+
+// Each year is `YEAR => module { days; alternates }`, and each day in it is
+// `dayN = N`. Days marked `#[stream]` also have stream_part1() and
+// stream_part2(), and days marked `#[params]` have a PARAMS list of names and
+// try_part1_with() and try_part2_with().
+macro_rules! solvers {
+    ($(
+        $year:literal => $module:ident {
+            $($(#[$attr:ident])* $day:ident = $n:literal),* $(,)?;
+            $(alternate $alt_day:ident = $alt_n:literal, $alt_part:literal, $alt_name:literal => $alt_fn:ident),* $(,)?
+        }
+    )*) => {
+        static SOLVERS: &[Solver] = &[
+            $(
+                $(
+                    Solver {
+                        year: $year,
+                        day: $n,
+                        part: 1,
+                        name: DEFAULT,
+                        run: |input| Answer::from(crate::$module::$day::part1(input)),
+                        try_run: Some(|input| crate::$module::$day::try_part1(input).map(Answer::from)),
+                        parse: Some(|input| crate::$module::$day::parse(input).map(drop)),
+                        stream: solvers!(@stream $module $day stream_part1 $($attr)*),
+                        params: solvers!(@params $module $day $($attr)*),
+                        with_params: solvers!(@with_params $module $day try_part1_with $($attr)*),
+                    },
+                    Solver {
+                        year: $year,
+                        day: $n,
+                        part: 2,
+                        name: DEFAULT,
+                        run: |input| Answer::from(crate::$module::$day::part2(input)),
+                        try_run: Some(|input| crate::$module::$day::try_part2(input).map(Answer::from)),
+                        parse: Some(|input| crate::$module::$day::parse(input).map(drop)),
+                        stream: solvers!(@stream $module $day stream_part2 $($attr)*),
+                        params: solvers!(@params $module $day $($attr)*),
+                        with_params: solvers!(@with_params $module $day try_part2_with $($attr)*),
+                    },
+                )*
+                $(
+                    Solver {
+                        year: $year,
+                        day: $alt_n,
+                        part: $alt_part,
+                        name: $alt_name,
+                        run: |input| Answer::from(crate::$module::$alt_day::$alt_fn(input)),
+                        try_run: None,
+                        parse: None,
+                        stream: None,
+                        params: &[],
+                        with_params: None,
+                    },
+                )*
+            )*
+        ];
+
+        static DAYS: &[Day] = &[
+            $(
+                $(
+                    Day {
+                        year: $year,
+                        day: $n,
+                        solve: |input, parts| {
+                            let start = Instant::now();
+                            let (parsed, parse_allocs) = allocs::measure(|| crate::$module::$day::parse(input));
+                            let parse_time = start.elapsed();
+                            let parsed = parsed?;
+                            let parts = parts.iter().map(|&part| {
+                                let start = Instant::now();
+                                let (answer, allocs) = allocs::measure(|| match part {
+                                    1 => crate::$module::$day::solve_part1(&parsed).into_answer(),
+                                    _ => crate::$module::$day::solve_part2(&parsed).into_answer(),
+                                });
+                                SolvedPart { part, answer, time: start.elapsed(), allocs }
+                            }).collect();
+                            Ok(Solved { parse_time, parse_allocs, parts })
+                        },
+                    },
+                )*
+            )*
+        ];
+
+        // A test for each day that checks its examples, named like
+        // `example_tests::y2024::day7`.
+        #[cfg(test)]
+        mod example_tests {
+            $(
+                mod $module {
+                    $(
+                        #[test]
+                        fn $day() {
+                            if let Err(e) = crate::examples::check($year, $n) {
+                                panic!("{}", e);
+                            }
+                        }
+                    )*
+                }
+            )*
+        }
+    };
+    (@stream $module:ident $day:ident $part:ident) => { None };
+    (@stream $module:ident $day:ident $part:ident stream $($rest:ident)*) => {
+        Some(|input| crate::$module::$day::$part(input).map(Answer::from))
+    };
+    (@stream $module:ident $day:ident $part:ident $other:ident $($rest:ident)*) => {
+        solvers!(@stream $module $day $part $($rest)*)
+    };
+    (@params $module:ident $day:ident) => { &[] };
+    (@params $module:ident $day:ident params $($rest:ident)*) => {
+        crate::$module::$day::PARAMS
+    };
+    (@params $module:ident $day:ident $other:ident $($rest:ident)*) => {
+        solvers!(@params $module $day $($rest)*)
+    };
+    (@with_params $module:ident $day:ident $part:ident) => { None };
+    (@with_params $module:ident $day:ident $part:ident params $($rest:ident)*) => {
+        Some(|input, params| crate::$module::$day::$part(input, params).map(Answer::from))
+    };
+    (@with_params $module:ident $day:ident $part:ident $other:ident $($rest:ident)*) => {
+        solvers!(@with_params $module $day $part $($rest)*)
+    };
+}
+
+solvers! {
+    2024 => y2024 {
+        #[stream] day1 = 1,
+        #[stream] day2 = 2,
+        day3 = 3,
+        day4 = 4,
+        day5 = 5,
+        day6 = 6,
+        #[stream] day7 = 7,
+        day8 = 8,
+        day9 = 9,
+        day10 = 10,
+        #[stream] #[params] day11 = 11,
+        day12 = 12,
+        #[stream] day13 = 13,
+        #[params] day14 = 14,
+        day15 = 15,
+        day16 = 16,
+        day17 = 17,
+        #[params] day18 = 18,
+        #[stream] day19 = 19,
+        #[params] day20 = 20,
+        day21 = 21,
+        #[stream] day22 = 22,
+        day23 = 23;
+        alternate day14 = 14, 1, "quad_prod_vec" => part1_quad_prod_vec,
+        alternate day14 = 14, 1, "quad_prod_vec_slow" => part1_quad_prod_vec_slow,
+    }
+}
+
+/// Every registered solver, ordered by day and part with the main
+/// implementations before the alternates.
+pub fn solvers() -> Vec<Solver> {
+    let mut solvers = SOLVERS.to_vec();
+    solvers.sort_by_key(|s| (s.year, s.day, s.part, !s.is_default()));
+    solvers
+}
+
+/// Every year that has solvers, in order.
+pub fn years() -> Vec<u32> {
+    let mut years: Vec<u32> = SOLVERS.iter().map(|s| s.year).collect();
+    years.sort_unstable();
+    years.dedup();
+    years
+}
+
+/// The main implementation of one part of a puzzle.
+pub fn find(year: u32, day: u32, part: u32) -> Option<Solver> {
+    SOLVERS.iter().find(|s| s.year == year && s.day == day && s.part == part && s.is_default()).copied()
+}
+
+/// Parses a day's input once and solves each of `parts` from it with the main
+/// implementations. Returns None if there is no such day.
+pub fn solve_parsed(year: u32, day: u32, input: &str, parts: &[u32]) -> Option<error::Result<Solved>> {
+    DAYS.iter().find(|d| d.year == year && d.day == day).map(|d| (d.solve)(input, parts))
+}
+
+/// A named implementation of one part of a puzzle.
+pub fn find_named(year: u32, day: u32, part: u32, name: &str) -> Option<Solver> {
+    SOLVERS.iter().find(|s| s.year == year && s.day == day && s.part == part && s.name == name).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solvers() {
+        let solvers = solvers();
+        let alternates = solvers.iter().filter(|s| s.day == 14 && s.part == 1 && !s.is_default()).count();
+        assert_eq!(2 * DAYS.len() + alternates, solvers.len());
+        assert_eq!(2 * DAYS.len(), solvers.iter().filter(|s| s.is_default()).count());
+        assert!(solvers.iter().all(|s| s.is_default() == s.try_run.is_some()));
+        assert!(solvers.iter().all(|s| s.is_default() == s.parse.is_some()));
+        assert_eq!(14, solvers.iter().filter(|s| s.stream.is_some()).count());
+        assert_eq!(8, solvers.iter().filter(|s| s.with_params.is_some()).count());
+        assert!(solvers.iter().all(|s| s.params.is_empty() == s.with_params.is_none()));
+        assert_eq!((1, 1), (solvers[0].day, solvers[0].part));
+        let last = solvers.last().unwrap();
+        assert_eq!(DAYS.iter().map(|d| (d.year, d.day, 2)).max(), Some((last.year, last.day, last.part)));
+        assert_eq!(vec![2024], years());
+
+        let day14: Vec<&str> = solvers.iter().filter(|s| s.day == 14 && s.part == 1).map(|s| s.name).collect();
+        assert_eq!(vec![DEFAULT, "quad_prod_vec", "quad_prod_vec_slow"], day14);
+    }
+
+    #[test]
+    fn test_find() {
+        let input = include_str!("../input/2024/day14.txt");
+        let expected = Answer::from(226179492u64);
+        assert_eq!(expected, (find(2024, 14, 1).unwrap().run)(input));
+        assert_eq!(Ok(expected.clone()), find_named(2024, 14, 1, "quad_prod_vec_slow").unwrap().try_solve(input));
+        assert!(find(2024, 26, 1).is_none());
+        assert!(find_named(2024, 14, 2, "quad_prod_vec").is_none());
+
+        let solved = solve_parsed(2024, 14, input, &[2, 1]).unwrap().unwrap();
+        assert_eq!(vec![2, 1], solved.parts.iter().map(|p| p.part).collect::<Vec<_>>());
+        assert_eq!(Ok(expected.clone()), solved.parts[1].answer);
+        assert_eq!(allocs::ENABLED, solved.parse_allocs.is_some());
+        assert!(solve_parsed(2024, 18, "1,x", &[1]).unwrap().is_err());
+        assert!(solve_parsed(2024, 26, input, &[1]).is_none());
+
+        let mut params = Params::new();
+        params.set("width", 11);
+        params.set("height", 7);
+        let example = "p=2,4 v=2,-3\np=9,5 v=-3,-3\n";
+        assert_eq!(Ok(Answer::from(0u64)), find(2024, 14, 1).unwrap().solve_with(example, &params));
+        params.set("depth", 3);
+        assert_eq!("day 14: expected one of `width`, `height`, found `depth`",
+            find(2024, 14, 1).unwrap().solve_with(example, &params).unwrap_err().to_string());
+        assert_eq!("day 7: expected no parameters, found `depth`",
+            find(2024, 7, 1).unwrap().solve_with(example, &params).unwrap_err().to_string());
+        params = Params::new();
+        params.set("dim", -1);
+        assert_eq!("day 18: expected a `dim` in range, found -1",
+            find(2024, 18, 1).unwrap().solve_with("1,1", &params).unwrap_err().to_string());
+
+        let stream = find(2024, 22, 2).unwrap().stream.unwrap();
+        assert_eq!(Ok(Answer::from(23u64)), stream(&mut "1\n2\n3\n2024".as_bytes()));
+    }
+}
@@ -0,0 +1,284 @@
+// Adding a new day for `aoc24 new-day`: the solver module from the
+// src/day0.rs template, its registration, and placeholders for its input,
+// answers and examples. Everything is done by editing the source as text, so
+// it has to run from the root of the repo.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = include_str!("day0.rs");
+
+// The part of the template that only makes sense in the template itself.
+const TEMPLATE_NOTE: &str = "\
+// This is the template that `aoc24 new-day` copies to src/yYYYY/dayN.rs,
+// replacing __YEAR__ and __DAY__. It isn't compiled itself.
+
+";
+
+// The year that `aoc_lib!` runs. Days from other years don't get `#[aoc]`
+// attributes, since cargo-aoc would take them for days of this one.
+const AOC_YEAR: u32 = 2024;
+
+/// What new_day() did to one file.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Change {
+    Created(PathBuf),
+    Updated(PathBuf),
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Change::Created(path) => write!(f, "created {}", path.display()),
+            Change::Updated(path) => write!(f, "updated {}", path.display()),
+        }
+    }
+}
+
+/// The source of a new day's module.
+pub fn template(year: u32, day: u32) -> String {
+    let text = TEMPLATE.replace(TEMPLATE_NOTE, "")
+        .replace("__YEAR__", &year.to_string())
+        .replace("__DAY__", &day.to_string());
+    if year == AOC_YEAR {
+        return text;
+    }
+    text.lines()
+        .filter(|line| !line.starts_with("#[aoc("))
+        .flat_map(|line| [line, "\n"])
+        .collect()
+}
+
+// The number in a line like `pub mod day7;`, if it is one.
+fn mod_number(line: &str, prefix: &str) -> Option<u32> {
+    line.strip_prefix("pub mod ")?.strip_prefix(prefix)?.strip_suffix(';')?.parse().ok()
+}
+
+// Adds `pub mod <prefix><n>;` among the others like it, in numeric order.
+fn insert_mod(text: &str, prefix: &str, n: u32) -> Result<String, String> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    let numbers: Vec<(usize, u32)> = lines.iter().enumerate()
+        .filter_map(|(i, line)| Some((i, mod_number(line, prefix)?)))
+        .collect();
+    if numbers.iter().any(|&(_, m)| m == n) {
+        return Err(format!("`pub mod {}{};` is already there", prefix, n));
+    }
+    let at = match numbers.iter().find(|&&(_, m)| m > n) {
+        Some(&(i, _)) => i,
+        None => numbers.last().map_or(lines.len(), |&(i, _)| i + 1),
+    };
+    let line = format!("pub mod {}{};", prefix, n);
+    lines.insert(at, &line);
+    Ok(lines.iter().flat_map(|line| [*line, "\n"]).collect())
+}
+
+// The day in a line of the solvers! list, like `#[stream] day7 = 7,`, and
+// whether it ends the list of days.
+fn registered_day(line: &str) -> Option<(u32, bool)> {
+    let mut line = line.trim();
+    while let Some(rest) = line.strip_prefix("#[") {
+        line = rest.split_once("] ")?.1;
+    }
+    let (_, n) = line.strip_prefix("day")?.split_once(" = ")?;
+    let (n, last) = match n.strip_suffix(';') {
+        Some(n) => (n, true),
+        None => (n.strip_suffix(',')?, false),
+    };
+    Some((n.parse().ok()?, last))
+}
+
+// Adds a day to the solvers! list in registry.rs, with a new block for the
+// year if it doesn't have one.
+fn register(text: &str, year: u32, day: u32) -> Result<String, String> {
+    let mut lines: Vec<String> = text.lines().map(String::from).collect();
+    let header = format!("    {} => y{} {{", year, year);
+    match lines.iter().position(|line| *line == header) {
+        Some(start) => {
+            let days: Vec<(usize, u32, bool)> = lines[start + 1..].iter().enumerate()
+                .map_while(|(i, line)| registered_day(line).map(|(n, last)| (start + 1 + i, n, last)))
+                .collect();
+            if days.iter().any(|&(_, n, _)| n == day) {
+                return Err(format!("{} day {} is already registered", year, day));
+            }
+            match days.iter().find(|&&(_, n, _)| n > day) {
+                Some(&(i, _, _)) => lines.insert(i, format!("        day{} = {},", day, day)),
+                None => {
+                    // The new day ends the list now.
+                    let &(i, _, _) = days.last().ok_or("the year has no days")?;
+                    lines[i] = lines[i].replacen(';', ",", 1);
+                    lines.insert(i + 1, format!("        day{} = {};", day, day));
+                },
+            }
+        },
+        None => {
+            let start = lines.iter().position(|line| line == "solvers! {").ok_or("no `solvers!` list")?;
+            let end = start + lines[start..].iter().position(|line| line == "}").ok_or("no end to the `solvers!` list")?;
+            lines.splice(end..end, [header, format!("        day{} = {};", day, day), "    }".to_string()]);
+        },
+    }
+    Ok(lines.iter().flat_map(|line| [line.as_str(), "\n"]).collect())
+}
+
+// Reads, edits and writes back a file.
+fn update(path: &Path, edit: impl FnOnce(&str) -> Result<String, String>) -> Result<Change, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("can't read {}: {}", path.display(), e))?;
+    let text = edit(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+    write(path, &text)?;
+    Ok(Change::Updated(path.to_path_buf()))
+}
+
+fn write(path: &Path, text: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("can't create {}: {}", dir.display(), e))?;
+    }
+    std::fs::write(path, text).map_err(|e| format!("can't write {}: {}", path.display(), e))
+}
+
+// Writes a file unless it is already there.
+fn create(path: PathBuf, text: &str, changes: &mut Vec<Change>) -> Result<(), String> {
+    if !path.exists() {
+        write(&path, text)?;
+        changes.push(Change::Created(path));
+    }
+    Ok(())
+}
+
+/// Adds a day to the repo at `root`, returning every file created or
+/// changed. Files other than the module that already exist are left alone.
+pub fn new_day(root: &Path, year: u32, day: u32) -> Result<Vec<Change>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("there is no day {}", day));
+    }
+    if !root.join("src/registry.rs").exists() {
+        return Err("`new-day` has to run from the root of the repo".to_string());
+    }
+    let module = root.join(format!("src/y{}/day{}.rs", year, day));
+    if module.exists() {
+        return Err(format!("{} already exists", module.display()));
+    }
+
+    // Check the edits before writing anything.
+    let registry = root.join("src/registry.rs");
+    let year_mod = root.join(format!("src/y{}/mod.rs", year));
+    register(&std::fs::read_to_string(&registry).unwrap_or_default(), year, day)
+        .map_err(|e| format!("{}: {}", registry.display(), e))?;
+
+    let mut changes = Vec::new();
+    write(&module, &template(year, day))?;
+    changes.push(Change::Created(module));
+    if year_mod.exists() {
+        changes.push(update(&year_mod, |text| insert_mod(text, "day", day))?);
+    } else {
+        write(&year_mod, &format!("// The {} puzzles, one module per day.\n\npub mod day{};\n", year, day))?;
+        changes.push(Change::Created(year_mod));
+        changes.push(update(&root.join("src/lib.rs"), |text| insert_mod(text, "y", year))?);
+    }
+    changes.push(update(&registry, |text| register(text, year, day))?);
+
+    create(root.join(format!("input/{}/day{}.txt", year, day)), "", &mut changes)?;
+    let table = format!("[day{}]\n# part1 = \n# part2 = \n", day);
+    let answers = root.join(format!("answers/{}.toml", year));
+    if !answers.exists() {
+        create(answers, &format!("# Expected answers for the {} puzzles.\n\n{}", year, table), &mut changes)?;
+    } else if !std::fs::read_to_string(&answers).unwrap_or_default().lines().any(|line| line == format!("[day{}]", day)) {
+        changes.push(update(&answers, |text| Ok(format!("{}\n{}", text, table)))?);
+    }
+    let examples = root.join(format!("examples/{}/day{}", year, day));
+    create(examples.join("example.txt"), "", &mut changes)?;
+    create(examples.join("example.toml"), "# The answers from the puzzle description.\n# part1 = \n# part2 = \n", &mut changes)?;
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Cut-down copies of the files that new_day() edits, so that the tests
+    // don't depend on which days the repo has.
+    const REGISTRY: &str = "\
+solvers! {
+    2024 => y2024 {
+        #[stream] day1 = 1,
+        day7 = 7,
+        #[stream] #[params] day11 = 11,
+        #[stream] day22 = 22,
+        day23 = 23;
+        alternate day14 = 14, 1, \"quad_prod_vec\" => part1_quad_prod_vec,
+    }
+}
+";
+    const LIB: &str = "pub mod grid;\npub mod registry;\n\npub mod y2024;\npub use y2024::*;\n";
+    const YEAR_MOD: &str = "// The 2024 puzzles, one module per day.\n\npub mod day1;\npub mod day7;\npub mod day23;\n";
+
+    #[test]
+    fn test_template() {
+        assert!(TEMPLATE.contains(TEMPLATE_NOTE));
+        let text = template(2024, 24);
+        assert!(text.starts_with("// https://adventofcode.com/2024/day/24\n\nuse crate::error;\n"));
+        assert!(text.contains("#[aoc(day24, part2)]\n"));
+        assert!(text.contains("expected_in(2024, 24, 2)"));
+        assert!(text.contains("include_str!(\"../../input/2024/day24.txt\")"));
+        assert!(!text.contains("__"));
+        assert!(!template(2025, 1).contains("#[aoc("));
+    }
+
+    #[test]
+    fn test_insert_mod() {
+        let text = "pub mod day1;\npub mod day3;\n";
+        assert_eq!(Ok("pub mod day1;\npub mod day2;\npub mod day3;\n".to_string()), insert_mod(text, "day", 2));
+        assert_eq!(Ok("pub mod day1;\npub mod day3;\npub mod day10;\n".to_string()), insert_mod(text, "day", 10));
+        assert_eq!(Err("`pub mod day3;` is already there".to_string()), insert_mod(text, "day", 3));
+        assert_eq!(Ok("pub mod grid;\npub mod y2024;\npub mod y2025;\npub use y2024::*;\n".to_string()),
+            insert_mod("pub mod grid;\npub mod y2024;\npub use y2024::*;\n", "y", 2025));
+    }
+
+    #[test]
+    fn test_register() {
+        let text = register(REGISTRY, 2024, 24).unwrap();
+        assert!(text.contains("        #[stream] day22 = 22,\n        day23 = 23,\n        day24 = 24;\n        alternate day14"));
+        let text = register(&text, 2025, 1).unwrap();
+        assert!(text.contains("\n    2025 => y2025 {\n        day1 = 1;\n    }\n}\n"));
+        let text = register(&text, 2025, 3).unwrap();
+        let text = register(&text, 2025, 2).unwrap();
+        assert!(text.contains("\n        day1 = 1,\n        day2 = 2,\n        day3 = 3;\n    }\n}\n"));
+        assert_eq!(Err("2024 day 7 is already registered".to_string()), register(&text, 2024, 7));
+        assert_eq!(Some((11, false)), registered_day("        #[stream] #[params] day11 = 11,"));
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = std::env::temp_dir().join("aoc24-test-scaffold");
+        let _ = std::fs::remove_dir_all(&root);
+        assert!(new_day(&root, 2025, 1).is_err());
+        write(&root.join("src/registry.rs"), REGISTRY).unwrap();
+        write(&root.join("src/lib.rs"), LIB).unwrap();
+        write(&root.join("src/y2024/mod.rs"), YEAR_MOD).unwrap();
+        write(&root.join("answers/2024.toml"), "[day1]\npart1 = 1\n").unwrap();
+
+        let changes = new_day(&root, 2025, 2).unwrap();
+        let names: Vec<String> = changes.iter().map(|c| c.to_string().replace(root.to_str().unwrap(), "")).collect();
+        assert_eq!(vec![
+            "created /src/y2025/day2.rs",
+            "created /src/y2025/mod.rs",
+            "updated /src/lib.rs",
+            "updated /src/registry.rs",
+            "created /input/2025/day2.txt",
+            "created /answers/2025.toml",
+            "created /examples/2025/day2/example.txt",
+            "created /examples/2025/day2/example.toml",
+        ], names);
+        let lib = std::fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        assert!(lib.contains("pub mod y2024;\npub mod y2025;\n"));
+
+        let changes = new_day(&root, 2024, 24).unwrap();
+        assert!(changes.contains(&Change::Updated(root.join("answers/2024.toml"))));
+        assert_eq!("[day1]\npart1 = 1\n\n[day24]\n# part1 = \n# part2 = \n",
+            std::fs::read_to_string(root.join("answers/2024.toml")).unwrap());
+        assert!(changes.contains(&Change::Updated(root.join("src/y2024/mod.rs"))));
+        let year_mod = std::fs::read_to_string(root.join("src/y2024/mod.rs")).unwrap();
+        assert!(year_mod.ends_with("pub mod day23;\npub mod day24;\n"));
+
+        assert!(new_day(&root, 2025, 2).unwrap_err().ends_with("day2.rs already exists"));
+        assert_eq!(Err("there is no day 26".to_string()), new_day(&root, 2025, 26));
+    }
+}
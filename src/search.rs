@@ -0,0 +1,229 @@
+// Generic shortest-path search (Dijkstra / A*) over an implicit graph.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::Hash;
+use std::ops::Add;
+use num_traits::Zero;
+use crate::trace::{self, Event};
+
+// A work queue entry. `priority` is the cost so far plus the heuristic.
+struct Node<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> Ord for Node<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Swap order to prioritize low-cost paths first.
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<S, C: Ord> PartialOrd for Node<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> PartialEq for Node<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Node<S, C> {}
+
+/// The result of a search. Every state that was reached has a distance, and
+/// every state other than a start has one or more predecessors. Together the
+/// predecessors form a DAG of all shortest paths.
+pub struct Search<S, C> {
+    dist: HashMap<S, C>,
+    preds: HashMap<S, Vec<S>>,
+    goals: Vec<S>,
+}
+
+impl<S, C> Search<S, C>
+    where S: Clone + Eq + Hash, C: Copy
+{
+    /// The shortest distance to `state`, if it was reached.
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.dist.get(state).copied()
+    }
+
+    pub fn distances(&self) -> &HashMap<S, C> {
+        &self.dist
+    }
+
+    /// All states that precede `state` on some shortest path.
+    pub fn predecessors(&self, state: &S) -> &[S] {
+        self.preds.get(state).map_or(&[], |p| p.as_slice())
+    }
+
+    /// The goal states that were reached at the lowest goal cost.
+    pub fn goals(&self) -> &[S] {
+        &self.goals
+    }
+
+    pub fn goal_cost(&self) -> Option<C> {
+        self.goals.first().and_then(|g| self.cost(g))
+    }
+
+    /// One shortest path from a start state to `state`, inclusive.
+    pub fn path(&self, state: &S) -> Option<Vec<S>> {
+        self.dist.get(state)?;
+        let mut path = vec![state.clone()];
+        while let Some(prev) = self.predecessors(path.last().unwrap()).first() {
+            path.push(prev.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every state on any shortest path from a start state to one of `ends`.
+    pub fn dag_states(&self, ends: impl IntoIterator<Item = S>) -> HashSet<S> {
+        let mut seen: HashSet<S> = HashSet::new();
+        let mut queue: Vec<S> = ends.into_iter().filter(|s| self.dist.contains_key(s)).collect();
+        while let Some(state) = queue.pop() {
+            if !seen.insert(state.clone()) {
+                continue;
+            }
+            queue.extend(self.predecessors(&state).iter().cloned());
+        }
+        seen
+    }
+}
+
+/// Dijkstra's algorithm. `neighbors` returns each state reachable from a state
+/// along with the cost to step there. The search stops once every state that
+/// could be on a shortest path to a goal has been expanded. Pass `|_| false`
+/// as `is_goal` to explore everything that is reachable.
+pub fn dijkstra<S, C, I>(
+        starts: impl IntoIterator<Item = S>,
+        neighbors: impl FnMut(&S) -> I,
+        is_goal: impl FnMut(&S) -> bool) -> Search<S, C>
+    where S: Clone + Eq + Hash,
+          C: Copy + Ord + Zero + Add<Output = C>,
+          I: IntoIterator<Item = (S, C)>
+{
+    astar(starts, neighbors, |_| C::zero(), is_goal)
+}
+
+/// A* search. `heuristic` must never overestimate the remaining cost to a goal
+/// and must be consistent, otherwise the results may not be shortest paths.
+/// Goal states are not expanded.
+pub fn astar<S, C, I>(
+        starts: impl IntoIterator<Item = S>,
+        mut neighbors: impl FnMut(&S) -> I,
+        mut heuristic: impl FnMut(&S) -> C,
+        mut is_goal: impl FnMut(&S) -> bool) -> Search<S, C>
+    where S: Clone + Eq + Hash,
+          C: Copy + Ord + Zero + Add<Output = C>,
+          I: IntoIterator<Item = (S, C)>
+{
+    let mut search = Search {
+        dist: HashMap::new(),
+        preds: HashMap::new(),
+        goals: Vec::new(),
+    };
+    let mut queue: BinaryHeap<Node<S, C>> = BinaryHeap::new();
+    for state in starts {
+        search.dist.insert(state.clone(), C::zero());
+        queue.push(Node { priority: heuristic(&state), cost: C::zero(), state });
+    }
+
+    let mut goal_cost: Option<C> = None;
+    // For tracing.
+    let (mut peak, mut expanded) = (queue.len(), 0);
+    while let Some(Node { priority, cost, state }) = queue.pop() {
+        if goal_cost.is_some_and(|g| priority > g) {
+            // Nothing left in the queue can be on a shortest path to a goal.
+            break;
+        }
+        if search.dist[&state] < cost {
+            // A cheaper path to this state was found after this was queued.
+            continue;
+        }
+        if is_goal(&state) {
+            goal_cost = Some(cost);
+            search.goals.push(state);
+            continue;
+        }
+        expanded += 1;
+        for (next, step) in neighbors(&state) {
+            let next_cost = cost + step;
+            match search.dist.get(&next) {
+                Some(d) if *d < next_cost => {},
+                Some(d) if *d == next_cost => {
+                    // Another shortest path to a state that is already queued.
+                    search.preds.entry(next).or_default().push(state.clone());
+                },
+                _ => {
+                    search.dist.insert(next.clone(), next_cost);
+                    search.preds.insert(next.clone(), vec![state.clone()]);
+                    queue.push(Node { priority: next_cost + heuristic(&next), cost: next_cost, state: next });
+                },
+            }
+        }
+        peak = peak.max(queue.len());
+    }
+    trace::emit(|| Event::Frontier { source: "search", peak, expanded });
+    search
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{Grid, Pos};
+
+    // A diamond with two equal-cost paths from 0 to 3, plus a detour.
+    fn edges(s: &u32) -> Vec<(u32, u32)> {
+        match s {
+            0 => vec![(1, 1), (2, 1), (4, 1)],
+            1 => vec![(3, 2)],
+            2 => vec![(3, 2)],
+            4 => vec![(3, 5)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let search = dijkstra([0], edges, |s| *s == 3);
+        assert_eq!(Some(3), search.goal_cost());
+        assert_eq!(&[3], search.goals());
+        assert_eq!(vec![1, 2], search.predecessors(&3).to_vec());
+        assert_eq!(Some(vec![0, 1, 3]), search.path(&3));
+        let mut dag: Vec<u32> = search.dag_states([3]).into_iter().collect();
+        dag.sort();
+        assert_eq!(vec![0, 1, 2, 3], dag);
+
+        let search = dijkstra([0], edges, |_| false);
+        assert_eq!(None, search.goal_cost());
+        assert_eq!(5, search.distances().len());
+        assert_eq!(Some(1), search.cost(&4));
+    }
+
+    #[test]
+    fn test_astar() {
+        let walls: Grid<bool> = Grid::from("\
+            ....\n\
+            .##.\n\
+            ..#.\n\
+            #...");
+        let end: Pos = (3, 3);
+        let search = astar(
+            [(0, 0)],
+            |p: &Pos| walls.neighbors4(*p)
+                .filter(|(_, n)| !walls[*n])
+                .map(|(_, n)| (n, 1))
+                .collect::<Vec<(Pos, u32)>>(),
+            |p| (p.0.abs_diff(end.0) + p.1.abs_diff(end.1)) as u32,
+            |p| *p == end);
+        assert_eq!(Some(6), search.goal_cost());
+        assert_eq!(7, search.path(&end).unwrap().len());
+        // Both the top-right and bottom-left routes are shortest.
+        assert_eq!(12, search.dag_states([end]).len());
+    }
+}
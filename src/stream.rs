@@ -0,0 +1,200 @@
+// Reading puzzle input from any BufRead, a line or a chunk of lines at a time,
+// for the solvers that can run on inputs too big to hold in memory.
+
+use std::io::{self, BufRead};
+use crate::error::{self, Error, Line};
+
+/// How many lines the chunked solvers hand to rayon at once.
+pub const CHUNK_LINES: usize = 4096;
+
+/// Numbers the lines of a BufRead for error reporting, like error::lines(),
+/// while only holding the current line or chunk in memory.
+pub struct LineReader<R> {
+    day: u32,
+    input: R,
+    // The number of lines read so far.
+    number: usize,
+    buf: String,
+    ends: Vec<usize>,
+}
+
+// Strips the line ending the same way str::lines() does.
+fn trim_newline(s: &str) -> &str {
+    match s.strip_suffix('\n') {
+        Some(s) => s.strip_suffix('\r').unwrap_or(s),
+        None => s,
+    }
+}
+
+impl<R: BufRead> LineReader<R> {
+    pub fn new(day: u32, input: R) -> Self {
+        LineReader { day, input, number: 0, buf: String::new(), ends: Vec::new() }
+    }
+
+    /// The number of the last line read.
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    fn read_error(&self, number: usize, e: io::Error) -> Error {
+        Error::new(self.day, number, 0, "readable input", e.to_string())
+    }
+
+    /// An error for input that stopped before something that was required.
+    pub fn end_of_input(&self, expected: impl Into<String>) -> Error {
+        Error::new(self.day, self.number + 1, 0, expected, "end of input")
+    }
+
+    /// The next line, or None at the end of the input.
+    pub fn next_line(&mut self) -> error::Result<Option<Line<'_>>> {
+        self.buf.clear();
+        match self.input.read_line(&mut self.buf) {
+            Ok(0) => Ok(None),
+            Ok(_) => {
+                self.number += 1;
+                Ok(Some(Line { day: self.day, number: self.number, text: trim_newline(&self.buf) }))
+            },
+            Err(e) => Err(self.read_error(self.number + 1, e)),
+        }
+    }
+
+    /// Calls `f` with each of the remaining lines.
+    pub fn for_each(&mut self, mut f: impl FnMut(Line) -> error::Result<()>) -> error::Result<()> {
+        while let Some(line) = self.next_line()? {
+            f(line)?;
+        }
+        Ok(())
+    }
+
+    /// Calls `f` with the remaining lines, up to `size` at a time.
+    pub fn for_each_chunk(&mut self, size: usize, mut f: impl FnMut(&[Line]) -> error::Result<()>) -> error::Result<()> {
+        loop {
+            self.buf.clear();
+            self.ends.clear();
+            while self.ends.len() < size {
+                match self.input.read_line(&mut self.buf) {
+                    Ok(0) => break,
+                    Ok(_) => self.ends.push(self.buf.len()),
+                    Err(e) => return Err(self.read_error(self.number + self.ends.len() + 1, e)),
+                }
+            }
+            if self.ends.is_empty() {
+                return Ok(());
+            }
+            let mut start = 0;
+            let lines: Vec<Line> = self.ends.iter().enumerate().map(|(i, &end)| {
+                let text = trim_newline(&self.buf[start..end]);
+                start = end;
+                Line { day: self.day, number: self.number + i + 1, text }
+            }).collect();
+            f(&lines)?;
+            self.number += self.ends.len();
+        }
+    }
+
+    /// Calls `f` with each whitespace-separated word of the remaining input,
+    /// as a Line holding just that word. Lines are never held whole, so this
+    /// works on a single line of any length. Errors from `f` are moved to the
+    /// word's real column.
+    pub fn for_each_word(&mut self, mut f: impl FnMut(Line) -> error::Result<()>) -> error::Result<()> {
+        let day = self.day;
+        let mut word: Vec<u8> = Vec::new();
+        // The line and column that the current word started at.
+        let (mut number, mut column, mut start) = (self.number + 1, 0, 0);
+        let mut flush = |word: &mut Vec<u8>, number: usize, start: usize| {
+            if word.is_empty() {
+                return Ok(());
+            }
+            let text = std::str::from_utf8(word)
+                .map_err(|_| Error::new(day, number, start, "UTF-8 text", "invalid UTF-8"))?;
+            f(Line { day, number, text }).map_err(|mut e| {
+                if e.column != 0 {
+                    e.column += start - 1;
+                }
+                e
+            })?;
+            word.clear();
+            Ok(())
+        };
+        loop {
+            let buf = match self.input.fill_buf() {
+                Ok(buf) => buf,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(self.read_error(number, e)),
+            };
+            if buf.is_empty() {
+                flush(&mut word, number, start)?;
+                self.number = number;
+                return Ok(());
+            }
+            let len = buf.len();
+            for &b in buf {
+                column += 1;
+                if b.is_ascii_whitespace() {
+                    flush(&mut word, number, start)?;
+                    if b == b'\n' {
+                        number += 1;
+                        column = 0;
+                    }
+                } else {
+                    if word.is_empty() {
+                        start = column;
+                    }
+                    word.push(b);
+                }
+            }
+            self.input.consume(len);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        let mut reader = LineReader::new(5, "a\r\nb\n\nc".as_bytes());
+        assert_eq!("a", reader.next_line().unwrap().unwrap().text);
+        let mut lines = Vec::new();
+        reader.for_each(|line| {
+            lines.push((line.number, line.text.to_string()));
+            Ok(())
+        }).unwrap();
+        assert_eq!(vec![(2, "b".to_string()), (3, String::new()), (4, "c".to_string())], lines);
+        assert!(reader.next_line().unwrap().is_none());
+        assert_eq!("day 5, line 5: expected more, found end of input", reader.end_of_input("more").to_string());
+    }
+
+    #[test]
+    fn test_chunks() {
+        let input: String = (1..=10).map(|i| format!("{}\n", i)).collect();
+        let mut reader = LineReader::new(1, io::BufReader::with_capacity(3, input.as_bytes()));
+        let mut chunks = Vec::new();
+        reader.for_each_chunk(4, |lines| {
+            chunks.push(lines.iter().map(|l| (l.number, l.text.parse::<usize>().unwrap())).collect::<Vec<_>>());
+            Ok(())
+        }).unwrap();
+        assert_eq!(vec![4, 4, 2], chunks.iter().map(Vec::len).collect::<Vec<_>>());
+        assert!(chunks.concat().iter().all(|(n, v)| n == v));
+
+        let err = LineReader::new(1, input.as_bytes()).for_each_chunk(3, |lines| {
+            lines.iter().try_for_each(|l| if l.text == "5" { Err(l.error_line("not 5")) } else { Ok(()) })
+        });
+        assert_eq!("day 1, line 5: expected not 5, found `5`", err.unwrap_err().to_string());
+    }
+
+    #[test]
+    fn test_words() {
+        // A tiny buffer, so that words span refills.
+        let input = "12 345\n  6789 x1";
+        let mut reader = LineReader::new(11, io::BufReader::with_capacity(2, input.as_bytes()));
+        let mut words = Vec::new();
+        let err = reader.for_each_word(|line| {
+            words.push(line.text.to_string());
+            line.parse::<u64>(line.text, "a number").map(drop)
+        });
+        assert_eq!(vec!["12", "345", "6789", "x1"], words);
+        assert_eq!("day 11, line 2, column 8: expected a number, found `x1`", err.unwrap_err().to_string());
+    }
+}
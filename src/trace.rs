@@ -0,0 +1,187 @@
+// Structured events from inside the solvers: grid snapshots, search frontier
+// sizes, iteration counts and cache hit rates. Nothing is recorded unless a
+// tracer is installed, and the solvers only build an event after checking
+// that one is, so tracing costs an atomic load when it is off.
+//
+// The tracer is global rather than passed to each solver, so that the solver
+// signatures stay `fn(&str) -> T` and events from rayon threads are kept.
+
+use std::fmt;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use crate::json;
+
+/// Set to `1` (or `text`) to print events to stderr, or `json` to print them
+/// as JSON lines.
+pub const ENV_VAR: &str = "AOC24_TRACE";
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Event {
+    /// A picture of a grid. `step` says when it was taken, like a move number.
+    Grid { source: &'static str, label: &'static str, step: u64, grid: String },
+    /// The most states a search had queued at once, and how many it expanded.
+    Frontier { source: &'static str, peak: usize, expanded: usize },
+    /// How many times a loop ran.
+    Iterations { source: &'static str, label: &'static str, count: u64 },
+    /// How often a lookup found an existing entry.
+    Cache { source: &'static str, label: &'static str, hits: u64, misses: u64 },
+}
+
+impl Event {
+    pub fn source(&self) -> &'static str {
+        match self {
+            Event::Grid { source, .. } | Event::Frontier { source, .. }
+                | Event::Iterations { source, .. } | Event::Cache { source, .. } => source,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        match self {
+            Event::Grid { source, label, step, grid } => format!(
+                "{{\"event\": \"grid\", \"source\": {}, \"label\": {}, \"step\": {}, \"grid\": {}}}",
+                json::string(source), json::string(label), step, json::string(grid)),
+            Event::Frontier { source, peak, expanded } => format!(
+                "{{\"event\": \"frontier\", \"source\": {}, \"peak\": {}, \"expanded\": {}}}",
+                json::string(source), peak, expanded),
+            Event::Iterations { source, label, count } => format!(
+                "{{\"event\": \"iterations\", \"source\": {}, \"label\": {}, \"count\": {}}}",
+                json::string(source), json::string(label), count),
+            Event::Cache { source, label, hits, misses } => format!(
+                "{{\"event\": \"cache\", \"source\": {}, \"label\": {}, \"hits\": {}, \"misses\": {}}}",
+                json::string(source), json::string(label), hits, misses),
+        }
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Event::Grid { source, label, step, grid } => write!(f, "{} {} at step {}:\n{}", source, label, step, grid),
+            Event::Frontier { source, peak, expanded } =>
+                write!(f, "{} frontier: peaked at {}, expanded {}", source, peak, expanded),
+            Event::Iterations { source, label, count } => write!(f, "{} {}: {} iterations", source, label, count),
+            Event::Cache { source, label, hits, misses } => {
+                let rate = *hits as f64 * 100.0 / (hits + misses).max(1) as f64;
+                write!(f, "{} {}: {} hits, {} misses ({:.1}% hit rate)", source, label, hits, misses, rate)
+            },
+        }
+    }
+}
+
+/// Receives events from the solvers. It can be called from several threads
+/// at once.
+pub trait Tracer: Send + Sync {
+    fn event(&self, event: &Event);
+}
+
+/// Prints each event to stderr, as text or as a JSON line.
+pub struct Printer {
+    pub json: bool,
+}
+
+impl Tracer for Printer {
+    fn event(&self, event: &Event) {
+        let mut err = std::io::stderr().lock();
+        let _ = if self.json {
+            writeln!(err, "{}", event.to_json())
+        } else {
+            writeln!(err, "trace: {}", event)
+        };
+    }
+}
+
+/// Keeps every event, for tests and for callers that want to look at them
+/// afterwards.
+#[derive(Default)]
+pub struct Recorder(Mutex<Vec<Event>>);
+
+impl Recorder {
+    pub fn events(&self) -> Vec<Event> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+impl Tracer for Recorder {
+    fn event(&self, event: &Event) {
+        self.0.lock().unwrap().push(event.clone());
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static TRACER: RwLock<Option<Arc<dyn Tracer>>> = RwLock::new(None);
+
+/// Installs `tracer` for every solver, or turns tracing off with None.
+pub fn set_tracer(tracer: Option<Arc<dyn Tracer>>) {
+    let mut current = TRACER.write().unwrap();
+    ENABLED.store(tracer.is_some(), Ordering::Relaxed);
+    *current = tracer;
+}
+
+/// The tracer asked for by the `AOC24_TRACE` environment variable, if any.
+pub fn from_env() -> Option<Arc<dyn Tracer>> {
+    match std::env::var(ENV_VAR).ok()?.as_str() {
+        "" | "0" => None,
+        "json" => Some(Arc::new(Printer { json: true })),
+        _ => Some(Arc::new(Printer { json: false })),
+    }
+}
+
+#[inline]
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Sends the event made by `event` to the tracer. `event` is only called when
+/// there is one.
+#[inline]
+pub fn emit(event: impl FnOnce() -> Event) {
+    if !enabled() {
+        return;
+    }
+    if let Some(tracer) = TRACER.read().unwrap().as_ref() {
+        tracer.event(&event());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Tracing is global, so everything that installs a tracer is in this one
+    // test. Other tests may still add events while it runs, so it only looks
+    // for the ones it expects.
+    #[test]
+    fn test_tracing() {
+        let recorder = Arc::new(Recorder::default());
+        set_tracer(Some(recorder.clone()));
+        crate::y2024::day14::part2(include_str!("../input/2024/day14.txt"));
+        crate::y2024::day16::part1(include_str!("../input/2024/day16.txt"));
+        crate::y2024::day11::part1("125 17");
+        set_tracer(None);
+        crate::y2024::day11::part2("125 17");
+        let events = recorder.events();
+
+        let tree = events.iter().find_map(|e| match e {
+            Event::Grid { source: "day14", label: "tree", step, grid } => Some((*step, grid)),
+            _ => None,
+        }).unwrap();
+        assert_eq!(crate::y2024::day14::part2(include_str!("../input/2024/day14.txt")), tree.0);
+        assert!(tree.1.contains("##########"));
+
+        assert!(events.iter().any(|e| matches!(e, Event::Grid { source: "day16", label: "maze", .. })));
+        assert!(events.iter().any(|e| matches!(e, Event::Frontier { source: "search", peak, expanded } if *peak > 0 && *expanded > 0)));
+        assert!(events.contains(&Event::Iterations { source: "day11", label: "blinks", count: 25 }));
+        assert!(events.contains(&Event::Cache { source: "day11", label: "stone counts", hits: 568, misses: 943 }));
+    }
+
+    #[test]
+    fn test_format() {
+        let event = Event::Cache { source: "day22", label: "patterns", hits: 3, misses: 1 };
+        assert_eq!("day22 patterns: 3 hits, 1 misses (75.0% hit rate)", event.to_string());
+        assert_eq!(r#"{"event": "cache", "source": "day22", "label": "patterns", "hits": 3, "misses": 1}"#, event.to_json());
+        let event = Event::Grid { source: "day15", label: "end", step: 2, grid: "#.\n".to_string() };
+        assert_eq!("day15 end at step 2:\n#.\n", event.to_string());
+        assert!(event.to_json().ends_with(r##""grid": "#.\n"}"##));
+    }
+}
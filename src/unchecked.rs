@@ -0,0 +1,41 @@
+// Unchecked slice access and compiler hints for the fast parsers in days 13
+// and 14. With the `checked` feature, and in debug builds, these turn back into
+// ordinary bounds checks and assertions, so that input the fast parsers don't
+// expect panics instead of being undefined behavior.
+
+#[cfg(any(feature = "checked", debug_assertions))]
+#[inline(always)]
+pub(crate) unsafe fn get<T>(slice: &[T], i: usize) -> &T {
+    &slice[i]
+}
+
+#[cfg(not(any(feature = "checked", debug_assertions)))]
+#[inline(always)]
+pub(crate) unsafe fn get<T>(slice: &[T], i: usize) -> &T {
+    slice.get_unchecked(i)
+}
+
+#[cfg(any(feature = "checked", debug_assertions))]
+#[inline(always)]
+pub(crate) unsafe fn get_mut<T>(slice: &mut [T], i: usize) -> &mut T {
+    &mut slice[i]
+}
+
+#[cfg(not(any(feature = "checked", debug_assertions)))]
+#[inline(always)]
+pub(crate) unsafe fn get_mut<T>(slice: &mut [T], i: usize) -> &mut T {
+    slice.get_unchecked_mut(i)
+}
+
+#[cfg(any(feature = "checked", debug_assertions))]
+#[inline(always)]
+#[track_caller]
+pub(crate) unsafe fn assume(cond: bool) {
+    assert!(cond);
+}
+
+#[cfg(not(any(feature = "checked", debug_assertions)))]
+#[inline(always)]
+pub(crate) unsafe fn assume(cond: bool) {
+    core::hint::assert_unchecked(cond);
+}
@@ -0,0 +1,504 @@
+// Frame-by-frame pictures of the simulations in days 6, 14, 15 and 16, written
+// as PPM or PNG image sequences or as an animated GIF.
+//
+// A frame is a grid of palette indices with the meanings below, so that every
+// day can share one palette. The encoders are written out here rather than
+// pulled in as dependencies. The PNGs use uncompressed deflate blocks, which
+// keeps them simple at the cost of size.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use crate::error;
+use crate::grid::Grid;
+
+pub type Frame = Grid<u8>;
+
+pub const EMPTY: u8 = 0;
+pub const WALL: u8 = 1;
+/// Somewhere that has been visited or searched.
+pub const VISITED: u8 = 2;
+/// The guard, a robot or the reindeer.
+pub const ACTOR: u8 = 3;
+pub const BOX: u8 = 4;
+/// The answer, like the best paths or the Christmas tree.
+pub const HIGHLIGHT: u8 = 5;
+
+pub type Rgb = [u8; 3];
+
+/// The color for each palette index.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Palette(pub Vec<Rgb>);
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette(vec![
+            [0x0f, 0x0f, 0x23], // EMPTY
+            [0x88, 0x88, 0x99], // WALL
+            [0x2e, 0x5e, 0x8e], // VISITED
+            [0xff, 0xcc, 0x00], // ACTOR
+            [0xb0, 0x6a, 0x2c], // BOX
+            [0x00, 0xcc, 0x44], // HIGHLIGHT
+        ])
+    }
+}
+
+impl Palette {
+    /// Parses comma-separated `rrggbb` colors, which replace the default
+    /// colors from index 0 on.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let mut palette = Palette::default();
+        for (i, color) in s.split(',').enumerate() {
+            let hex = color.trim().trim_start_matches('#');
+            let rgb = u32::from_str_radix(hex, 16).ok().filter(|_| hex.len() == 6)
+                .ok_or_else(|| format!("`{}` isn't a color like `ff8800`", color))?;
+            let rgb = [(rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8];
+            if i < palette.0.len() {
+                palette.0[i] = rgb;
+            } else {
+                palette.0.push(rgb);
+            }
+        }
+        if palette.0.len() > 256 {
+            return Err("a palette can have at most 256 colors".to_string());
+        }
+        Ok(palette)
+    }
+
+    // Indices past the end of the palette get the last color.
+    fn index(&self, i: u8) -> u8 {
+        i.min(self.0.len() as u8 - 1)
+    }
+
+    pub fn color(&self, i: u8) -> Rgb {
+        self.0[self.index(i) as usize]
+    }
+}
+
+/// Keeps the frames of a simulation, at most `limit` of them counting the
+/// final one. When it fills up it drops every other frame and keeps half as
+/// many from then on, so the frames always span the whole simulation evenly.
+pub struct Recorder {
+    limit: usize,
+    every: usize,
+    step: usize,
+    frames: Vec<Frame>,
+}
+
+impl Recorder {
+    pub fn new(limit: usize) -> Self {
+        // One place is saved for the final frame.
+        Recorder { limit: limit.max(2) - 1, every: 1, step: 0, frames: Vec::new() }
+    }
+
+    /// Offers the frame for the next step. `frame` is only called if the
+    /// frame is kept.
+    pub fn step(&mut self, frame: impl FnOnce() -> Frame) {
+        if self.step.is_multiple_of(self.every) {
+            if self.frames.len() == self.limit {
+                let mut i = 0;
+                self.frames.retain(|_| { i += 1; i % 2 == 1 });
+                self.every *= 2;
+            }
+            if self.step.is_multiple_of(self.every) {
+                self.frames.push(frame());
+            }
+        }
+        self.step += 1;
+    }
+
+    /// Adds a frame that is always kept, like the final state.
+    pub fn finish(&mut self, frame: Frame) {
+        self.frames.push(frame);
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+}
+
+/// The recorder for a day, if it has one.
+pub fn recorder(day: u32) -> Option<fn(&str, &mut Recorder) -> error::Result<()>> {
+    match day {
+        6 => Some(crate::y2024::day6::record),
+        14 => Some(crate::y2024::day14::record),
+        15 => Some(crate::y2024::day15::record),
+        16 => Some(crate::y2024::day16::record),
+        _ => None,
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Format {
+    Ppm,
+    Png,
+    Gif,
+}
+
+impl Format {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "ppm" => Some(Format::Ppm),
+            "png" => Some(Format::Png),
+            "gif" => Some(Format::Gif),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Ppm => "ppm",
+            Format::Png => "png",
+            Format::Gif => "gif",
+        }
+    }
+}
+
+/// How to write frames out.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Config {
+    pub format: Format,
+    pub palette: Palette,
+    /// The width and height of each cell, in pixels.
+    pub scale: usize,
+    /// The most frames to keep.
+    pub frames: usize,
+    /// How long each frame of a GIF is shown, in hundredths of a second.
+    pub delay: u16,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config { format: Format::Gif, palette: Palette::default(), scale: 4, frames: 500, delay: 4 }
+    }
+}
+
+// The palette index of every pixel, row by row, with each cell blown up to
+// `scale` by `scale` pixels.
+fn pixels(frame: &Frame, palette: &Palette, scale: usize) -> Vec<u8> {
+    let mut res = Vec::with_capacity(frame.width() * frame.height() * scale * scale);
+    for row in frame.rows() {
+        let line: Vec<u8> = row.iter()
+            .flat_map(|&i| std::iter::repeat_n(palette.index(i), scale))
+            .collect();
+        for _ in 0..scale {
+            res.extend_from_slice(&line);
+        }
+    }
+    res
+}
+
+pub fn write_ppm(frame: &Frame, palette: &Palette, scale: usize, out: &mut impl Write) -> io::Result<()> {
+    write!(out, "P6\n{} {}\n255\n", frame.width() * scale, frame.height() * scale)?;
+    let rgb: Vec<u8> = pixels(frame, palette, scale).into_iter().flat_map(|i| palette.color(i)).collect();
+    out.write_all(&rgb)
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut table = [0u32; 256];
+    for (n, entry) in table.iter_mut().enumerate() {
+        let mut c = n as u32;
+        for _ in 0..8 {
+            c = if c & 1 == 1 { 0xedb88320 ^ (c >> 1) } else { c >> 1 };
+        }
+        *entry = c;
+    }
+    !data.iter().fold(!0u32, |c, &b| table[((c ^ b as u32) & 0xff) as usize] ^ (c >> 8))
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+fn png_chunk(out: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    let body = [kind.as_slice(), data].concat();
+    out.write_all(&body)?;
+    out.write_all(&crc32(&body).to_be_bytes())
+}
+
+/// An indexed-color PNG.
+pub fn write_png(frame: &Frame, palette: &Palette, scale: usize, out: &mut impl Write) -> io::Result<()> {
+    let (width, height) = (frame.width() * scale, frame.height() * scale);
+    out.write_all(b"\x89PNG\r\n\x1a\n")?;
+    let mut header = Vec::new();
+    header.extend_from_slice(&(width as u32).to_be_bytes());
+    header.extend_from_slice(&(height as u32).to_be_bytes());
+    // 8 bits per pixel, indexed color, no interlacing.
+    header.extend_from_slice(&[8, 3, 0, 0, 0]);
+    png_chunk(out, b"IHDR", &header)?;
+    png_chunk(out, b"PLTE", &palette.0.concat())?;
+
+    // Each row starts with filter type 0 (none).
+    let pixels = pixels(frame, palette, scale);
+    let raw: Vec<u8> = pixels.chunks(width.max(1)).flat_map(|row| std::iter::once(0).chain(row.iter().copied())).collect();
+    // A zlib stream of stored deflate blocks.
+    let mut zlib = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = if raw.is_empty() { vec![&[]] } else { raw.chunks(65535).collect() };
+    for (i, block) in blocks.iter().enumerate() {
+        zlib.push((i + 1 == blocks.len()) as u8);
+        zlib.extend_from_slice(&(block.len() as u16).to_le_bytes());
+        zlib.extend_from_slice(&(!(block.len() as u16)).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+    zlib.extend_from_slice(&adler32(&raw).to_be_bytes());
+    png_chunk(out, b"IDAT", &zlib)?;
+    png_chunk(out, b"IEND", &[])
+}
+
+// Packs variable-width codes into bytes, least significant bit first.
+struct BitWriter {
+    bytes: Vec<u8>,
+    acc: u32,
+    bits: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u32) {
+        self.acc |= (code as u32) << self.bits;
+        self.bits += size;
+        while self.bits >= 8 {
+            self.bytes.push(self.acc as u8);
+            self.acc >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.acc as u8);
+        }
+        self.bytes
+    }
+}
+
+// GIF's variant of LZW, with codes of up to 12 bits.
+fn lzw(data: &[u8], min_size: u32) -> Vec<u8> {
+    let clear: u16 = 1 << min_size;
+    let end = clear + 1;
+    let mut out = BitWriter { bytes: Vec::new(), acc: 0, bits: 0 };
+    let mut dict: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next = end + 1;
+    let mut size = min_size + 1;
+    out.write(clear, size);
+    let mut prefix: Option<u16> = None;
+    for &b in data {
+        let Some(p) = prefix else {
+            prefix = Some(b as u16);
+            continue;
+        };
+        if let Some(&code) = dict.get(&(p, b)) {
+            prefix = Some(code);
+            continue;
+        }
+        out.write(p, size);
+        if next < 4096 {
+            dict.insert((p, b), next);
+            next += 1;
+            if next > (1 << size) && size < 12 {
+                size += 1;
+            }
+        } else {
+            out.write(clear, size);
+            dict.clear();
+            next = end + 1;
+            size = min_size + 1;
+        }
+        prefix = Some(b as u16);
+    }
+    if let Some(p) = prefix {
+        out.write(p, size);
+    }
+    out.write(end, size);
+    out.finish()
+}
+
+/// An animated GIF that loops forever.
+pub fn write_gif(frames: &[Frame], palette: &Palette, scale: usize, delay: u16, out: &mut impl Write) -> io::Result<()> {
+    let (width, height) = frames.first().map_or((0, 0), |f| (f.width() * scale, f.height() * scale));
+    // The color table must have a power of two entries, at least 2.
+    let bits = (palette.0.len().max(2) as u32).next_power_of_two().trailing_zeros();
+    out.write_all(b"GIF89a")?;
+    out.write_all(&(width as u16).to_le_bytes())?;
+    out.write_all(&(height as u16).to_le_bytes())?;
+    out.write_all(&[0x80 | ((bits - 1) << 4) as u8 | (bits - 1) as u8, 0, 0])?;
+    let mut table = palette.0.concat();
+    table.resize(3 << bits, 0);
+    out.write_all(&table)?;
+    // Loop forever.
+    out.write_all(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00")?;
+
+    let min_size = bits.max(2);
+    for frame in frames {
+        out.write_all(&[0x21, 0xf9, 0x04, 0x00])?;
+        out.write_all(&delay.to_le_bytes())?;
+        out.write_all(&[0x00, 0x00])?;
+        out.write_all(&[0x2c, 0, 0, 0, 0])?;
+        out.write_all(&((frame.width() * scale) as u16).to_le_bytes())?;
+        out.write_all(&((frame.height() * scale) as u16).to_le_bytes())?;
+        out.write_all(&[0x00, min_size as u8])?;
+        for block in lzw(&pixels(frame, palette, scale), min_size).chunks(255) {
+            out.write_all(&[block.len() as u8])?;
+            out.write_all(block)?;
+        }
+        out.write_all(&[0x00])?;
+    }
+    out.write_all(&[0x3b])
+}
+
+/// Writes `frames` to `path`: one animated GIF, or a directory of numbered
+/// PPM or PNG files. Returns the files written.
+pub fn save(frames: &[Frame], config: &Config, path: &Path) -> io::Result<Vec<PathBuf>> {
+    let create = |path: &Path| std::fs::File::create(path).map(io::BufWriter::new);
+    if config.format == Format::Gif {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut out = create(path)?;
+        write_gif(frames, &config.palette, config.scale, config.delay, &mut out)?;
+        out.flush()?;
+        return Ok(vec![path.to_path_buf()]);
+    }
+    std::fs::create_dir_all(path)?;
+    let mut paths = Vec::new();
+    for (i, frame) in frames.iter().enumerate() {
+        let file = path.join(format!("frame{:05}.{}", i, config.format.extension()));
+        let mut out = create(&file)?;
+        match config.format {
+            Format::Ppm => write_ppm(frame, &config.palette, config.scale, &mut out)?,
+            _ => write_png(frame, &config.palette, config.scale, &mut out)?,
+        }
+        out.flush()?;
+        paths.push(file);
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame() -> Frame {
+        Grid::from_fn(3, 2, |(y, x)| (y * 3 + x) as u8)
+    }
+
+    #[test]
+    fn test_palette() {
+        let palette = Palette::parse("#ffffff,000001").unwrap();
+        assert_eq!([0xff, 0xff, 0xff], palette.color(EMPTY));
+        assert_eq!([0, 0, 1], palette.color(WALL));
+        assert_eq!(Palette::default().color(HIGHLIGHT), palette.color(HIGHLIGHT));
+        assert_eq!(palette.color(HIGHLIGHT), palette.color(200));
+        assert_eq!(Err("`fff` isn't a color like `ff8800`".to_string()), Palette::parse("fff"));
+    }
+
+    #[test]
+    fn test_recorder() {
+        let mut recorder = Recorder::new(4);
+        for i in 0..10 {
+            recorder.step(|| Grid::new(1, 1, i));
+        }
+        recorder.finish(Grid::new(1, 1, 99));
+        let steps: Vec<u8> = recorder.frames().iter().map(|f| f[(0, 0)]).collect();
+        assert_eq!(vec![0, 4, 8, 99], steps);
+    }
+
+    #[test]
+    fn test_ppm() {
+        let mut out = Vec::new();
+        write_ppm(&frame(), &Palette::default(), 2, &mut out).unwrap();
+        assert!(out.starts_with(b"P6\n6 4\n255\n"));
+        assert_eq!(11 + 6 * 4 * 3, out.len());
+        assert_eq!(Palette::default().color(5).as_slice(), &out[out.len() - 3..]);
+    }
+
+    #[test]
+    fn test_png() {
+        let mut out = Vec::new();
+        write_png(&frame(), &Palette::default(), 1, &mut out).unwrap();
+        assert!(out.starts_with(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x03\0\0\0\x02\x08\x03"));
+        // The standard empty IEND chunk.
+        assert!(out.ends_with(b"\0\0\0\0IEND\xae\x42\x60\x82"));
+        let idat = out.windows(4).position(|w| w == b"IDAT").unwrap() + 4;
+        // The zlib header, one final stored block of 2 rows of 1 + 3 bytes, and
+        // then the rows themselves.
+        assert_eq!(&[0x78, 0x01, 1, 8, 0, 0xf7, 0xff, 0, 0, 1, 2, 0, 3, 4, 5], &out[idat..idat + 15]);
+        assert_eq!(0x414fa339, crc32(b"The quick brown fox jumps over the lazy dog"));
+    }
+
+    // A GIF LZW decoder, to check the encoder against.
+    fn unlzw(data: &[u8], min_size: u32) -> Vec<u8> {
+        let clear = 1usize << min_size;
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let reset = |table: &mut Vec<Vec<u8>>| {
+            *table = (0..clear).map(|i| vec![i as u8]).collect();
+            table.push(Vec::new());
+            table.push(Vec::new());
+        };
+        reset(&mut table);
+        let (mut size, mut pos, mut prev): (u32, usize, Option<Vec<u8>>) = (min_size + 1, 0, None);
+        let mut res = Vec::new();
+        loop {
+            let code = (0..size).fold(0, |c, i| {
+                let bit = (data[(pos + i as usize) / 8] >> ((pos + i as usize) % 8)) & 1;
+                c | (bit as usize) << i
+            });
+            pos += size as usize;
+            if code == clear {
+                reset(&mut table);
+                size = min_size + 1;
+                prev = None;
+                continue;
+            }
+            if code == clear + 1 {
+                return res;
+            }
+            let entry = match (&prev, table.get(code)) {
+                (_, Some(e)) => e.clone(),
+                (Some(p), None) => [p.clone(), vec![p[0]]].concat(),
+                (None, None) => panic!("bad code"),
+            };
+            if let Some(p) = prev {
+                if table.len() < 4096 {
+                    table.push([p, vec![entry[0]]].concat());
+                }
+                if table.len() == 1 << size && size < 12 {
+                    size += 1;
+                }
+            }
+            res.extend_from_slice(&entry);
+            prev = Some(entry);
+        }
+    }
+
+    #[test]
+    fn test_lzw() {
+        let mut rng = crate::gen::Rng::new(1);
+        // Long enough to fill the table several times.
+        let noise: Vec<u8> = (0..50000).map(|_| rng.below(8) as u8).collect();
+        let runs: Vec<u8> = (0..50000).map(|i| (i / 300 % 4) as u8).collect();
+        for data in [vec![], vec![1], noise, runs] {
+            assert_eq!(data, unlzw(&lzw(&data, 3), 3));
+        }
+    }
+
+    #[test]
+    fn test_gif() {
+        let mut out = Vec::new();
+        write_gif(&[frame(), frame()], &Palette::default(), 1, 5, &mut out).unwrap();
+        // 3x2, with a global table of 8 colors.
+        assert!(out.starts_with(b"GIF89a\x03\0\x02\0\xa2\0\0"));
+        assert!(out.ends_with(b"\x3b"));
+        assert_eq!(2, out.windows(4).filter(|w| *w == [0x21, 0xf9, 0x04, 0x00]).count());
+
+        let dir = std::env::temp_dir().join("aoc24-test-viz");
+        let config = Config { format: Format::Png, ..Config::default() };
+        let paths = save(&[frame(), frame()], &config, &dir).unwrap();
+        assert_eq!(vec![dir.join("frame00000.png"), dir.join("frame00001.png")], paths);
+    }
+}
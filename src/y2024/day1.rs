@@ -0,0 +1,391 @@
+
+// https://adventofcode.com/2024/day/1
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::BufRead;
+use crate::error::{self, lines, Line};
+use crate::stream::LineReader;
+
+// What to call the ID in column `i` in an error message.
+fn column_name(i: usize) -> String {
+    match i {
+        0 => "a location ID".to_string(),
+        1 => "a second location ID".to_string(),
+        2 => "a third location ID".to_string(),
+        _ => format!("location ID {}", i + 1),
+    }
+}
+
+// Reads the IDs on one line into `row`, and returns false for a blank line.
+// With `columns`, the line must have exactly that many; otherwise it can have
+// any number.
+fn parse_row(line: &Line, columns: Option<usize>, row: &mut Vec<i64>) -> error::Result<bool> {
+    row.clear();
+    if line.text.trim().is_empty() {
+        return Ok(false);
+    }
+    // Split the input string into words and try to parse each word as an integer
+    let mut words = line.text.split_whitespace();
+    while columns.is_none_or(|n| row.len() < n) {
+        let word = match (words.next(), columns) {
+            (Some(word), _) => word,
+            (None, None) => break,
+            (None, Some(_)) => line.field(None, column_name(row.len()))?,
+        };
+        row.push(line.parse(word, "a location ID")?);
+    }
+    if let Some(word) = words.next() {
+        return Err(line.error(word, "the end of the line"));
+    }
+    Ok(true)
+}
+
+/// Parallel lists of IDs, one for each column of the input. Each list is kept
+/// in the order it was given and also sorted, since most of the metrics only
+/// need the sorted lists.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Lists {
+    columns: Vec<Vec<i64>>,
+    sorted: Vec<Vec<i64>>,
+}
+
+/// One of the ways that Lists::compare() can compare two lists.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Metric {
+    /// The sum of the distances between the sorted lists (part 1).
+    L1,
+    /// The Euclidean distance between the sorted lists.
+    L2,
+    /// Each ID in the first list times how often it is in the second (part 2).
+    Similarity,
+    /// How many IDs the lists have in common, counting repeats.
+    Intersection,
+    /// Spearman's rank correlation between the lists as given, from -1 to 1.
+    RankCorrelation,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 5] = [Metric::L1, Metric::L2, Metric::Similarity, Metric::Intersection, Metric::RankCorrelation];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Metric::L1 => "l1",
+            Metric::L2 => "l2",
+            Metric::Similarity => "similarity",
+            Metric::Intersection => "intersection",
+            Metric::RankCorrelation => "rank-correlation",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Metric::ALL.into_iter().find(|m| m.name() == s)
+    }
+}
+
+/// The result of a metric, which is exact for the ones that count or sum.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Score {
+    Int(i128),
+    Real(f64),
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Score::Int(n) => write!(f, "{}", n),
+            Score::Real(x) => write!(f, "{}", x),
+        }
+    }
+}
+
+// Each distinct value in a sorted list, with how many times it is there.
+fn runs(sorted: &[i64]) -> impl Iterator<Item = (i64, u64)> + '_ {
+    sorted.chunk_by(|x, y| x == y).map(|run| (run[0], run.len() as u64))
+}
+
+// The similarity score from the counts of each ID in the two lists, both in
+// order of ID. The in-memory and streaming solvers both add it up this way,
+// so they overflow on the same input. The counts grow with the length of the
+// input, which isn't bounded when streaming, so even an i128 can overflow.
+fn similarity(xs: impl Iterator<Item = (i64, u64)>, ys: impl Iterator<Item = (i64, u64)>) -> error::Result<i128> {
+    let mut ys = ys.peekable();
+    let mut total: i128 = 0;
+    for (x, count) in xs {
+        while ys.next_if(|&(y, _)| y < x).is_some() {}
+        let Some((_, val)) = ys.next_if(|&(y, _)| y == x) else {
+            continue;
+        };
+        total = (x as i128).checked_mul(val as i128)
+            .and_then(|n| n.checked_mul(count as i128))
+            .and_then(|n| total.checked_add(n))
+            .ok_or_else(|| error::Error::new(1, 0, 0, "a similarity score that fits in an i128", "an overflow"))?;
+    }
+    Ok(total)
+}
+
+// The rank of each value in `values`, starting from 1, where tied values share
+// the average of the ranks they cover.
+fn ranks(values: &[i64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_unstable_by_key(|&i| values[i]);
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    for run in order.chunk_by(|&i, &j| values[i] == values[j]) {
+        let rank = start as f64 + (run.len() as f64 + 1.0) / 2.0;
+        for &i in run {
+            ranks[i] = rank;
+        }
+        start += run.len();
+    }
+    ranks
+}
+
+impl Lists {
+    /// How many lists there are.
+    pub fn count(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// List `i` in the order it was given.
+    pub fn column(&self, i: usize) -> &[i64] {
+        &self.columns[i]
+    }
+
+    /// List `i`, sorted.
+    pub fn sorted(&self, i: usize) -> &[i64] {
+        &self.sorted[i]
+    }
+
+    pub fn l1_distance(&self, a: usize, b: usize) -> u128 {
+        self.sorted[a].iter().zip(&self.sorted[b]).map(|(x, y)| x.abs_diff(*y) as u128).sum()
+    }
+
+    pub fn l2_distance(&self, a: usize, b: usize) -> f64 {
+        let squares: f64 = self.sorted[a].iter().zip(&self.sorted[b]).map(|(x, y)| (x.abs_diff(*y) as f64).powi(2)).sum();
+        squares.sqrt()
+    }
+
+    pub fn similarity(&self, a: usize, b: usize) -> error::Result<i128> {
+        similarity(runs(&self.sorted[a]), runs(&self.sorted[b]))
+    }
+
+    pub fn intersection(&self, a: usize, b: usize) -> usize {
+        // Walk both sorted lists together, pairing off equal IDs.
+        let (xs, ys) = (&self.sorted[a], &self.sorted[b]);
+        let (mut i, mut j, mut count) = (0, 0, 0);
+        while i < xs.len() && j < ys.len() {
+            match xs[i].cmp(&ys[j]) {
+                std::cmp::Ordering::Less => i += 1,
+                std::cmp::Ordering::Greater => j += 1,
+                std::cmp::Ordering::Equal => {
+                    count += 1;
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// None if either list has every ID the same, or there are fewer than two
+    /// rows, when the correlation isn't defined.
+    pub fn rank_correlation(&self, a: usize, b: usize) -> Option<f64> {
+        let (ra, rb) = (ranks(&self.columns[a]), ranks(&self.columns[b]));
+        let n = ra.len() as f64;
+        let (mean_a, mean_b) = (ra.iter().sum::<f64>() / n, rb.iter().sum::<f64>() / n);
+        let (mut cov, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+        for (x, y) in ra.iter().zip(&rb) {
+            cov += (x - mean_a) * (y - mean_b);
+            var_a += (x - mean_a).powi(2);
+            var_b += (y - mean_b).powi(2);
+        }
+        (var_a > 0.0 && var_b > 0.0).then(|| cov / (var_a * var_b).sqrt())
+    }
+
+    /// Compares list `a` with list `b`, or returns None if the metric isn't
+    /// defined for them or the score overflows.
+    pub fn compare(&self, a: usize, b: usize, metric: Metric) -> Option<Score> {
+        Some(match metric {
+            Metric::L1 => Score::Int(self.l1_distance(a, b) as i128),
+            Metric::L2 => Score::Real(self.l2_distance(a, b)),
+            Metric::Similarity => Score::Int(self.similarity(a, b).ok()?),
+            Metric::Intersection => Score::Int(self.intersection(a, b) as i128),
+            Metric::RankCorrelation => Score::Real(self.rank_correlation(a, b)?),
+        })
+    }
+}
+
+/// Parses lines of whitespace-separated IDs into one list per column. The
+/// number of columns is `columns` if given, otherwise however many the first
+/// line has, and every line must have the same.
+pub fn parse_lists(input: &str, columns: Option<usize>) -> error::Result<Lists> {
+    let mut lists: Vec<Vec<i64>> = columns.map(|n| vec![Vec::new(); n]).unwrap_or_default();
+    let mut row: Vec<i64> = Vec::new();
+    for line in lines(1, input) {
+        let width = (!lists.is_empty()).then_some(lists.len());
+        if !parse_row(&line, width, &mut row)? {
+            continue;
+        }
+        if lists.is_empty() {
+            lists = vec![Vec::new(); row.len()];
+        }
+        for (list, &id) in lists.iter_mut().zip(&row) {
+            list.push(id);
+        }
+    }
+    let sorted = lists.iter().map(|list| {
+        let mut list = list.clone();
+        list.sort_unstable();
+        list
+    }).collect();
+    Ok(Lists { columns: lists, sorted })
+}
+
+/// The two lists of location IDs.
+pub type Input = Lists;
+
+pub fn parse(input: &str) -> error::Result<Input> {
+    parse_lists(input, Some(2))
+}
+
+pub fn solve_part1(lists: &Input) -> u128 {
+    lists.l1_distance(0, 1)
+}
+
+pub fn solve_part2(lists: &Input) -> error::Result<i128> {
+    lists.similarity(0, 1)
+}
+
+pub fn try_part1(input: &str) -> error::Result<u128> {
+    Ok(solve_part1(&parse(input)?))
+}
+
+pub fn try_part2(input: &str) -> error::Result<i128> {
+    solve_part2(&parse(input)?)
+}
+
+// Counts how often each location ID appears in each list, which is all that
+// either part needs. Memory grows with the number of distinct IDs, not with
+// the number of lines.
+fn count_lists(input: impl BufRead) -> error::Result<(BTreeMap<i64, u64>, BTreeMap<i64, u64>)> {
+    let mut counts1: BTreeMap<i64, u64> = BTreeMap::new();
+    let mut counts2: BTreeMap<i64, u64> = BTreeMap::new();
+    let mut row: Vec<i64> = Vec::with_capacity(2);
+    LineReader::new(1, input).for_each(|line| {
+        if parse_row(&line, Some(2), &mut row)? {
+            *counts1.entry(row[0]).or_default() += 1;
+            *counts2.entry(row[1]).or_default() += 1;
+        }
+        Ok(())
+    })?;
+    Ok((counts1, counts2))
+}
+
+pub fn stream_part1(input: impl BufRead) -> error::Result<u128> {
+    let (counts1, counts2) = count_lists(input)?;
+
+    // Walk both sorted lists together, pairing off the IDs in order. Like the
+    // zip in try_part1(), this stops at the end of the shorter list.
+    let mut counts2 = counts2.into_iter();
+    let mut current: Option<(i64, u64)> = None;
+    let mut total: u128 = 0;
+    for (a, mut count) in counts1 {
+        while count > 0 {
+            if current.is_none_or(|(_, left)| left == 0) {
+                current = counts2.next();
+            }
+            let Some((b, left)) = &mut current else {
+                return Ok(total);
+            };
+            let pairs = count.min(*left);
+            total += a.abs_diff(*b) as u128 * pairs as u128;
+            count -= pairs;
+            *left -= pairs;
+        }
+    }
+    Ok(total)
+}
+
+pub fn stream_part2(input: impl BufRead) -> error::Result<i128> {
+    let (counts1, counts2) = count_lists(input)?;
+    similarity(counts1.into_iter(), counts2.into_iter())
+}
+
+#[aoc(day1, part1)]
+pub fn part1(input: &str) -> u128 {
+    try_part1(input).unwrap()
+}
+
+#[aoc(day1, part2)]
+pub fn part2(input: &str) -> i128 {
+    try_part2(input).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::expected;
+
+    static TEST_INPUT: &str = include_str!("../../examples/2024/day1/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Ok(11), stream_part1(TEST_INPUT.as_bytes()));
+        assert_eq!(Ok(8), stream_part1("1 1\n1 1\n9 2\n3 2\n".as_bytes()));
+        assert_eq!("day 1, line 2, column 2: expected a second location ID, found end of line",
+            try_part1("3   4\n4").unwrap_err().to_string());
+
+        assert_eq!(expected(1, 1), part1(include_str!("../../input/2024/day1.txt")).into());
+        assert_eq!(expected(1, 1), stream_part1(include_bytes!("../../input/2024/day1.txt").as_slice()).unwrap().into());
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Ok(31), stream_part2(TEST_INPUT.as_bytes()));
+
+        assert_eq!(expected(1, 2), part2(include_str!("../../input/2024/day1.txt")).into());
+        assert_eq!(expected(1, 2), stream_part2(include_bytes!("../../input/2024/day1.txt").as_slice()).unwrap().into());
+    }
+
+    #[test]
+    fn test_metrics() {
+        let lists = parse(TEST_INPUT).unwrap();
+        assert_eq!(2, lists.count());
+        assert_eq!(&[3, 4, 2, 1, 3, 3], lists.column(0));
+        assert_eq!(&[3, 3, 3, 4, 5, 9], lists.sorted(1));
+        assert_eq!(11, lists.l1_distance(0, 1));
+        assert_eq!(35f64.sqrt(), lists.l2_distance(0, 1));
+        assert_eq!(Ok(31), lists.similarity(0, 1));
+        assert_eq!(4, lists.intersection(0, 1));
+        assert!((lists.rank_correlation(0, 1).unwrap() + 3.0 / 31.0).abs() < 1e-12);
+        assert_eq!(Some(1.0), lists.rank_correlation(1, 1));
+        assert_eq!(Some(Score::Int(11)), lists.compare(0, 1, Metric::L1));
+        assert_eq!(Some(Score::Int(31)), lists.compare(0, 1, Metric::Similarity));
+        assert!(Metric::ALL.iter().all(|&m| Metric::parse(m.name()) == Some(m)));
+
+        // Any number of columns, taken from the first line.
+        let lists = parse_lists("1 -2 3\n\n2 -4 1\n3 -6 2\n", None).unwrap();
+        assert_eq!(3, lists.count());
+        assert_eq!(Some(-1.0), lists.rank_correlation(0, 1));
+        assert_eq!(Some(Score::Real(-0.5)), lists.compare(0, 2, Metric::RankCorrelation));
+        assert_eq!(3, lists.intersection(0, 2));
+        assert_eq!(None, parse_lists("1 1\n2 1", None).unwrap().compare(0, 1, Metric::RankCorrelation));
+
+        // Sums past the range of an i64.
+        let (min, max) = (i64::MIN, i64::MAX);
+        let extremes = format!("{min} {max}\n{min} {max}\n{max} {max}\n");
+        let lists = parse(&extremes).unwrap();
+        assert_eq!(2 * u64::MAX as u128, lists.l1_distance(0, 1));
+        assert_eq!(Ok(2 * u64::MAX as u128), stream_part1(extremes.as_bytes()));
+        assert_eq!(Ok(3 * max as i128), lists.similarity(0, 1));
+        assert_eq!(Ok(3 * max as i128), stream_part2(extremes.as_bytes()));
+        let l2 = parse(&format!("{min} {max}\n{min} {max}\n")).unwrap().l2_distance(0, 1);
+        assert!((l2 / (2f64.sqrt() * u64::MAX as f64) - 1.0).abs() < 1e-12);
+        assert_eq!(0, parse_lists("", None).unwrap().count());
+        assert_eq!("day 1, line 2, column 4: expected a third location ID, found end of line",
+            parse_lists("1 2 3\n4 5", None).unwrap_err().to_string());
+        assert_eq!("day 1, line 2, column 5: expected the end of the line, found `5`",
+            parse_lists("1 2\n3 4 5", None).unwrap_err().to_string());
+    }
+}
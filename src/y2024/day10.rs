@@ -0,0 +1,120 @@
+
+// https://adventofcode.com/2024/day/10
+
+use std::collections::HashSet;
+use crate::error;
+use crate::grid::{Grid, Pos};
+
+// Heights are kept as ASCII digits. Some of the examples use `.` for tiles
+// that can't be reached, which never match any height.
+pub type Input = Grid<u8>;
+
+pub fn parse(input: &str) -> error::Result<Input> {
+    Grid::try_parse_with(10, input, |_, b| {
+        if b.is_ascii_digit() || b == b'.' { Ok(b) } else { Err("a height") }
+    })
+}
+
+pub fn solve_part1(heights: &Input) -> u64 {
+    let mut total: u64 = 0;
+
+    // This structure stores the set of trail ends that are accessible from each
+    // point.
+    // TODO: The list of trail ends accessible from each point could be stored
+    // as a bit vector instead of a HashSet. The set of trail ends accessible
+    // from each point is limited to points that are within <10 manhattan
+    // distance and on the correct checkerboard color. This means that only a
+    // relatively small bit vector is required for each point (<128 bits
+    // optimally or up less than 512 in a convenient layout).
+    let mut ends: Grid<HashSet<Pos>> = heights.map(|_| HashSet::new());
+
+    for pass in (b'0'..b'0'+10).rev() {
+        for pos in heights.positions() {
+            if heights[pos] != pass {
+                continue;
+            }
+            if pass == b'9' {
+                ends[pos].insert(pos);
+                continue;
+            }
+
+            let mut reachable = HashSet::new();
+            for (_, n) in heights.neighbors4(pos) {
+                if heights[n] == pass + 1 {
+                    reachable.extend(ends[n].iter().copied());
+                }
+            }
+            if pass == b'0' {
+                total += reachable.len() as u64;
+            }
+            ends[pos] = reachable;
+        }
+    }
+
+    total
+}
+
+pub fn solve_part2(heights: &Input) -> u64 {
+    let mut total: u64 = 0;
+
+    // TODO: The accumulation could be done with SIMD instructions.
+    let mut ratings: Grid<u64> = heights.map(|_| 0);
+
+    for pass in (b'0'..b'0'+10).rev() {
+        for pos in heights.positions() {
+            if heights[pos] != pass {
+                continue;
+            }
+            if pass == b'9' {
+                ratings[pos] = 1;
+                continue;
+            }
+
+            let rating = heights.neighbors4(pos)
+                .filter(|(_, n)| heights[*n] == pass + 1)
+                .map(|(_, n)| ratings[n])
+                .sum();
+            ratings[pos] = rating;
+
+            if pass == b'0' {
+                total += rating;
+            }
+        }
+    }
+
+    total
+}
+
+pub fn try_part1(input: &str) -> error::Result<u64> {
+    Ok(solve_part1(&parse(input)?))
+}
+
+pub fn try_part2(input: &str) -> error::Result<u64> {
+    Ok(solve_part2(&parse(input)?))
+}
+
+#[aoc(day10, part1)]
+pub fn part1(input: &str) -> u64 {
+    try_part1(input).unwrap()
+}
+
+#[aoc(day10, part2)]
+pub fn part2(input: &str) -> u64 {
+    try_part2(input).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::expected;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(expected(10, 1), part1(include_str!("../../input/2024/day10.txt")).into());
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(expected(10, 2), part2(include_str!("../../input/2024/day10.txt")).into());
+    }
+}
@@ -0,0 +1,151 @@
+
+// https://adventofcode.com/2024/day/11
+
+use std::collections::HashMap;
+use std::io::BufRead;
+use crate::error::{self, lines};
+use crate::registry::Params;
+use crate::stream::LineReader;
+use crate::trace::{self, Event};
+
+fn blink(counts: HashMap<u64, u64>) -> HashMap<u64, u64> {
+    // Each stone splits at most once per blink, so this is guaranteed to never
+    // resize.
+    let mut new_counts: HashMap<u64, u64> = HashMap::with_capacity(counts.len() * 2);
+    for (&key, &value) in counts.iter() {
+        if key == 0 {
+            let count = new_counts.entry(1).or_insert(0);
+            *count += value;
+            continue;
+        }
+
+        // Check if the key has an even number of digits.
+        let log: u32 = key.ilog10();
+        if log % 2 == 1 {
+            let digits: u32 = log.div_ceil(2);
+            let cut = u32::pow(10, digits) as u64;
+            let count = new_counts.entry(key / cut).or_insert(0);
+            *count += value;
+            let count = new_counts.entry(key % cut).or_insert(0);
+            *count += value;
+        } else {
+            let count = new_counts.entry(key * 2024).or_insert(0);
+            *count += value;
+        }
+    }
+    new_counts
+}
+
+fn blink_counts(iters: u32, mut counts: HashMap<u64, u64>) -> u64 {
+    // A hit is a stone that joins an engraving already in the next map.
+    let (mut hits, mut misses) = (0, 0);
+    for _ in 0..iters {
+        let inserts: u64 = if trace::enabled() {
+            counts.keys().map(|&k| if k != 0 && k.ilog10() % 2 == 1 { 2 } else { 1 }).sum()
+        } else {
+            0
+        };
+        counts = blink(counts);
+        misses += counts.len() as u64;
+        hits += inserts.saturating_sub(counts.len() as u64);
+    }
+    trace::emit(|| Event::Iterations { source: "day11", label: "blinks", count: iters as u64 });
+    trace::emit(|| Event::Cache { source: "day11", label: "stone counts", hits, misses });
+    counts.values().sum::<u64>()
+}
+
+/// How many stones have each engraving.
+pub type Input = HashMap<u64, u64>;
+
+pub fn parse(input: &str) -> error::Result<Input> {
+    // counts maps a stone engraving to a frequency for that engraving.
+    let mut counts: HashMap<u64, u64> = HashMap::with_capacity(10);
+    for line in lines(11, input) {
+        for word in line.text.split_whitespace() {
+            let count = counts.entry(line.parse(word, "a stone number")?).or_insert(0);
+            *count += 1;
+        }
+    }
+    Ok(counts)
+}
+
+// Counts the stones a word at a time, so that a line of any length can be
+// read. Memory grows with the number of distinct engravings.
+fn stream_blinks(iters: u32, input: impl BufRead) -> error::Result<u64> {
+    let mut counts: HashMap<u64, u64> = HashMap::with_capacity(10);
+    LineReader::new(11, input).for_each_word(|word| {
+        *counts.entry(word.parse(word.text, "a stone number")?).or_insert(0) += 1;
+        Ok(())
+    })?;
+    Ok(blink_counts(iters, counts))
+}
+
+pub fn solve_part1(counts: &Input) -> u64 {
+    blink_counts(25, counts.clone())
+}
+
+pub fn solve_part2(counts: &Input) -> u64 {
+    blink_counts(75, counts.clone())
+}
+
+pub fn try_part1(input: &str) -> error::Result<u64> {
+    Ok(solve_part1(&parse(input)?))
+}
+
+pub fn try_part2(input: &str) -> error::Result<u64> {
+    Ok(solve_part2(&parse(input)?))
+}
+
+/// `blinks` overrides the number of times the stones change.
+pub const PARAMS: &[&str] = &["blinks"];
+
+pub fn try_part1_with(input: &str, params: &Params) -> error::Result<u64> {
+    Ok(blink_counts(params.get(11, "blinks", 25)?, parse(input)?))
+}
+
+pub fn try_part2_with(input: &str, params: &Params) -> error::Result<u64> {
+    Ok(blink_counts(params.get(11, "blinks", 75)?, parse(input)?))
+}
+
+pub fn stream_part1(input: impl BufRead) -> error::Result<u64> {
+    stream_blinks(25, input)
+}
+
+pub fn stream_part2(input: impl BufRead) -> error::Result<u64> {
+    stream_blinks(75, input)
+}
+
+#[aoc(day11, part1)]
+pub fn part1(input: &str) -> u64 {
+    try_part1(input).unwrap()
+}
+
+#[aoc(day11, part2)]
+pub fn part2(input: &str) -> u64 {
+    try_part2(input).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::expected;
+
+    static TEST_INPUT: &str = include_str!("../../examples/2024/day11/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Ok(55312), stream_part1(TEST_INPUT.as_bytes()));
+        assert_eq!("day 11, line 1, column 5: expected a stone number, found `x`",
+            stream_part1("125 x".as_bytes()).unwrap_err().to_string());
+
+        assert_eq!(expected(11, 1), part1(include_str!("../../input/2024/day11.txt")).into());
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(parse(TEST_INPUT).map(|counts| blink_counts(75, counts)), Ok(part2(TEST_INPUT)));
+
+        assert_eq!(expected(11, 2), part2(include_str!("../../input/2024/day11.txt")).into());
+        assert_eq!(expected(11, 2), stream_part2(include_bytes!("../../input/2024/day11.txt").as_slice()).unwrap().into());
+    }
+}
@@ -0,0 +1,122 @@
+
+// https://adventofcode.com/2024/day/12
+
+use std::collections::LinkedList;
+use crate::error;
+use crate::grid::{Direction, Grid, Pos};
+
+/// The garden, and the plots of each region.
+pub struct Input {
+    pub garden: Grid<u8>,
+    pub regions: Vec<Vec<Pos>>,
+}
+
+pub fn parse(input: &str) -> error::Result<Input> {
+    let garden = Grid::try_parse_with(12, input, |_, b| {
+        if b.is_ascii_uppercase() { Ok(b) } else { Err("a plant type") }
+    })?;
+    let mut seen: Grid<bool> = garden.map(|_| false);
+    // A work queue for finding all the entries in a blob.
+    let mut queue: LinkedList<Pos> = LinkedList::new();
+    let mut regions = Vec::new();
+
+    for pos in garden.positions() {
+        if seen[pos] {
+            continue;
+        }
+        let b = garden[pos];
+        let mut region = Vec::new();
+        // seed the work queue.
+        queue.push_back(pos);
+        while let Some(p) = queue.pop_front() {
+            if seen[p] {
+                continue;
+            }
+            seen[p] = true;
+            region.push(p);
+            for (_, n) in garden.neighbors4(p) {
+                if garden[n] == b {
+                    queue.push_back(n); // Fill
+                }
+            }
+        }
+        regions.push(region);
+    }
+    Ok(Input { garden, regions })
+}
+
+pub fn solve_part1(Input { garden, regions }: &Input) -> u64 {
+    let mut total = 0;
+    for region in regions {
+        let b = garden[region[0]];
+        // Every side that doesn't lead to the same plant (including the edge
+        // of the map) is part of the perimeter.
+        let perimeter: usize = region.iter()
+            .map(|&p| 4 - garden.neighbors4(p).filter(|(_, n)| garden[*n] == b).count())
+            .sum();
+        total += (region.len() * perimeter) as u64;
+    }
+    total
+}
+
+pub fn solve_part2(Input { garden, regions }: &Input) -> u64 {
+    let mut total = 0;
+    for region in regions {
+        let b = garden[region[0]];
+        let same = |p: Pos, delta: (isize, isize)| {
+            garden.offset(p, delta).is_some_and(|n| garden[n] == b)
+        };
+        let mut perimeter = 0;
+        for &p in region {
+            // Count corners instead of edges. Each corner of this cell is
+            // between one direction and the direction clockwise from it.
+            for dir in Direction::ALL {
+                let a = dir.delta();
+                let c = dir.turn_right().delta();
+                match (same(p, a), same(p, c)) {
+                    (false, false) => perimeter += 1, // Convex corner
+                    (true, true) if !same(p, (a.0 + c.0, a.1 + c.1)) => {
+                        perimeter += 1; // Concave corner
+                    },
+                    _ => {},
+                }
+            }
+        }
+        total += region.len() as u64 * perimeter;
+    }
+    total
+}
+
+pub fn try_part1(input: &str) -> error::Result<u64> {
+    Ok(solve_part1(&parse(input)?))
+}
+
+pub fn try_part2(input: &str) -> error::Result<u64> {
+    Ok(solve_part2(&parse(input)?))
+}
+
+#[aoc(day12, part1)]
+pub fn part1(input: &str) -> u64 {
+    try_part1(input).unwrap()
+}
+
+#[aoc(day12, part2)]
+pub fn part2(input: &str) -> u64 {
+    try_part2(input).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::expected;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(expected(12, 1), part1(include_str!("../../input/2024/day12.txt")).into());
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(expected(12, 2), part2(include_str!("../../input/2024/day12.txt")).into());
+    }
+}
@@ -0,0 +1,492 @@
+
+// https://adventofcode.com/2024/day/13
+
+// use scanf::sscanf;
+use std::cmp::Ordering;
+use std::io::BufRead;
+use crate::error::{self, lines, Line};
+use crate::stream::LineReader;
+use crate::unchecked::{assume, get};
+
+// Returns None if the total doesn't fit in an i64. fits_fast_path() keeps the
+// numbers small enough that nothing else can overflow.
+unsafe fn get_total(input: &str, part2: bool) -> Option<i64> {
+    let mut x1: Vec<i64> = Vec::with_capacity(320);
+    let mut y1: Vec<i64> = Vec::with_capacity(320);
+    let mut x2: Vec<i64> = Vec::with_capacity(320);
+    let mut y2: Vec<i64> = Vec::with_capacity(320);
+    let mut x3: Vec<i64> = Vec::with_capacity(320);
+    let mut y3: Vec<i64> = Vec::with_capacity(320);
+
+    for line in input.lines() {
+        let bytes = line.as_bytes();
+        // This quite dirty parsing is 10x faster than sscanf :/
+        if let Some(b) = bytes.get(7) {
+            if *b == b'A' {
+                // sscanf!(line, "Button A: X+{}, Y+{}", x, y).unwrap();
+                let mut x = (get(bytes, 12) - b'0') as i64;
+                let mut pos = 13;
+                let mut b = get(bytes, pos);
+                while *b != b',' {
+                    x = 10 * x + (*b - b'0') as i64;
+                    pos += 1;
+                    b = get(bytes, pos);
+                }
+                pos += 4;
+                let mut y = (get(bytes, pos) - b'0') as i64;
+                pos += 1;
+                while pos < bytes.len() {
+                    y = 10 * y + (get(bytes, pos) - b'0') as i64;
+                    pos += 1;
+                }
+                x1.push(x);
+                y1.push(y);
+            } else if *b == b'B' {
+                // sscanf!(line, "Button B: X+{}, Y+{}", x, y).unwrap();
+                let mut x = (get(bytes, 12) - b'0') as i64;
+                let mut pos = 13;
+                let mut b = get(bytes, pos);
+                while *b != b',' {
+                    x = 10 * x + (*b - b'0') as i64;
+                    pos += 1;
+                    b = get(bytes, pos);
+                }
+                pos += 4;
+                let mut y = (get(bytes, pos) - b'0') as i64;
+                pos += 1;
+                while pos < bytes.len() {
+                    y = 10 * y + (get(bytes, pos) - b'0') as i64;
+                    pos += 1;
+                }
+                x2.push(x);
+                y2.push(y);
+            } else if *b == b'X' {
+                // sscanf!(line, "Prize: X={}, Y={}", x, y).unwrap();
+                let mut x = (get(bytes, 9) - b'0') as i64;
+                let mut pos = 10;
+                let mut b = get(bytes, pos);
+                while *b != b',' {
+                    x = 10 * x + (*b - b'0') as i64;
+                    pos += 1;
+                    b = get(bytes, pos);
+                }
+                pos += 4;
+                let mut y = (get(bytes, pos) - b'0') as i64;
+                pos += 1;
+                while pos < bytes.len() {
+                    y = 10 * y + (get(bytes, pos) - b'0') as i64;
+                    pos += 1;
+                }
+                if part2 {
+                    x += 10000000000000;
+                    y += 10000000000000;
+                }
+                x3.push(x);
+                y3.push(y);
+            }
+        }
+    }
+    let count: usize = x1.len();
+    let mut total: Vec<i64> = vec![0; count];
+    let total: &mut [i64] = &mut total.as_mut_slice()[0..count];
+
+    // Solve a system of equations for a and b:
+    // a * x1 + b * x2 = x3
+    // a * y1 + b * y2 = y3
+    // a in 0..101
+    // b in 0..101
+    // If there are no solutions, return 0;
+    // If there is 1 solution return 3 * a + b;
+    // If there is more than one solution, return the smallest 3 * a + b;
+    //
+    // Consider the following transformations:
+    // a * x1 * y2 + b * x2 * y2 = x3 * y2
+    // a * x2 * y1 + b * x2 * y2 = x2 * y3
+    // a * (x1 * y2 - x2 * y1) = (x3 * y2 - x2 * y3)
+    // a = (x3 * y2 - x2 * y3) / (x1 * y2 - x2 * y1)
+    // a = na / da
+    // 
+    // a * x1 * y1 + b * x2 * y1 = x3 * y1
+    // a * x1 * y1 + b * x1 * y2 = x1 * y3
+    // b * (x2 * y1 - x1 * y2) = (x3 * y1 - x1 * y3)
+    // b = (x3 * y1 - x1 * y3) / (x2 * y1 - x1 * y2)
+    // b = nb / db
+    //
+    // if na == da == nd == db == 0, there may be multiple solutions. Handle this case special.
+    // else if da == 0 or db == 0, there are no solutions.
+    // else there is one possible solution. Check if a and b are ints in the correct range.
+    
+    // TODO: Is this correctly autovectorized?
+    #[allow(clippy::needless_range_loop)]
+    for c in 0..count {
+        let x1 = get(&x1, c);
+        let y1 = get(&y1, c);
+        let x2 = get(&x2, c);
+        let y2 = get(&y2, c);
+        let x3 = get(&x3, c);
+        let y3 = get(&y3, c);
+        let na = (x3 * y2) - (x2 * y3);
+        let da = (x1 * y2) - (x2 * y1);
+        let nb = (x3 * y1) - (x1 * y3);
+        let db = 0 - da; // Note the expressions above are the same but reversed.
+        debug_assert!(da != 0);
+        assume(da != 0);
+        let a = na / da;
+        assume(db != 0);
+        let b = nb / db;
+        let in_range = part2 || (a <= 100 && b <= 100);
+        total[c] = if 0 <= a && 0 <= b && in_range && a * da == na && b * db == nb {
+            3 * a + b
+        } else {
+            0
+        }
+    }
+        
+    total.iter().try_fold(0i64, |sum, &t| sum.checked_add(t))
+}
+
+// Reads an unsigned number of at most 5 digits from the start of `s`. In part
+// 2 the prize gains 10^13, and with any more digits its products in
+// get_total() could overflow an i64.
+fn fast_number(s: &[u8]) -> Option<(i64, &[u8])> {
+    let len = s.iter().take_while(|b| b.is_ascii_digit()).count();
+    if !(1..=5).contains(&len) {
+        return None;
+    }
+    Some((s[..len].iter().fold(0, |n, b| 10 * n + (b - b'0') as i64), &s[len..]))
+}
+
+fn fast_pair(line: &str, prefix: &[u8], sep: u8) -> Option<(i64, i64)> {
+    let rest = line.as_bytes().strip_prefix(prefix)?;
+    let (x, rest) = fast_number(rest)?;
+    let rest = rest.strip_prefix(b", Y")?.strip_prefix(&[sep])?;
+    let (y, rest) = fast_number(rest)?;
+    rest.is_empty().then_some((x, y))
+}
+
+// Checks everything that get_total() assumes about its input without checking:
+// every non-empty line is part of a "Button A", "Button B", "Prize" group, the
+// numbers are unsigned and short, and no machine's buttons move in the same
+// direction.
+fn fits_fast_path(input: &str) -> bool {
+    let mut lines = input.lines().filter(|line| !line.is_empty());
+    while let Some(line) = lines.next() {
+        let a = fast_pair(line, b"Button A: X+", b'+');
+        let b = lines.next().and_then(|line| fast_pair(line, b"Button B: X+", b'+'));
+        let prize = lines.next().and_then(|line| fast_pair(line, b"Prize: X=", b'='));
+        match (a, b, prize) {
+            (Some(a), Some(b), Some(_)) if a.0 * b.1 != a.1 * b.0 => (),
+            _ => return false,
+        }
+    }
+    true
+}
+
+/// A claw machine: the moves made by the A and B buttons, and the prize
+/// location.
+pub struct Machine {
+    pub a: (i64, i64),
+    pub b: (i64, i64),
+    pub prize: (i64, i64),
+}
+
+pub type Input = Vec<Machine>;
+
+// Parse one line like "Button A: X+94, Y+34" or "Prize: X=8400, Y=5400".
+fn parse_pair(line: &Line, prefix: &str, sep: &str) -> error::Result<(i64, i64)> {
+    let rest = line.expect(line.text, prefix)?;
+    let rest = line.expect(rest, "X")?;
+    let rest = line.expect(rest, sep)?;
+    let (x, rest) = line.split_once(rest, ",")?;
+    let rest = line.expect(rest, " Y")?;
+    let y = line.expect(rest, sep)?;
+    Ok((line.parse(x, "a number")?, line.parse(y, "a number")?))
+}
+
+// A checked version of the parsing in get_total(), which reports malformed
+// input instead of reading out of bounds.
+pub fn parse(input: &str) -> error::Result<Input> {
+    let mut machines = Vec::new();
+    let mut lines = lines(13, input).filter(|line| !line.text.is_empty());
+    while let Some(line) = lines.next() {
+        let a = parse_pair(&line, "Button A: ", "+")?;
+        let line = lines.next().ok_or_else(|| error::end_of_input(13, input, "`Button B: `"))?;
+        let b = parse_pair(&line, "Button B: ", "+")?;
+        let line = lines.next().ok_or_else(|| error::end_of_input(13, input, "`Prize: `"))?;
+        let prize = parse_pair(&line, "Prize: ", "=")?;
+        machines.push(Machine { a, b, prize });
+    }
+    Ok(machines)
+}
+
+// The fewest tokens that win the prize from one machine, or 0 if it can't be
+// won. This works in i128, where each product fits but the sums of them
+// might not, and returns None if anything overflows.
+fn solve(m: &Machine, part2: bool) -> Option<i128> {
+    let offset = if part2 { 10000000000000 } else { 0 };
+    let ((x1, y1), (x2, y2)) = ((m.a.0 as i128, m.a.1 as i128), (m.b.0 as i128, m.b.1 as i128));
+    let (x3, y3) = (m.prize.0 as i128 + offset, m.prize.1 as i128 + offset);
+    // See get_total() for the derivation.
+    let na = (x3 * y2).checked_sub(x2 * y3)?;
+    let da = (x1 * y2).checked_sub(x2 * y1)?;
+    if da == 0 {
+        return solve_colinear(((x1, y1), (x2, y2)), (x3, y3), (!part2).then_some(100));
+    }
+    let nb = (x3 * y1).checked_sub(x1 * y3)?;
+    let db = da.checked_neg()?;
+    let (a, b) = (na.checked_div(da)?, nb.checked_div(db)?);
+    let in_range = part2 || (a <= 100 && b <= 100);
+    if 0 <= a && 0 <= b && in_range && a * da == na && b * db == nb { a.checked_mul(3)?.checked_add(b) } else { Some(0) }
+}
+
+fn floor_div(n: i128, d: i128) -> i128 {
+    let q = n / d;
+    if n % d != 0 && (n < 0) != (d < 0) { q - 1 } else { q }
+}
+
+// The gcd of `a` and `b`, and s and t such that s * a + t * b is the gcd.
+fn ext_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut r0, mut r1, mut s0, mut s1, mut t0, mut t1) = (a, b, 1, 0, 0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (s0, s1) = (s1, s0 - q * s1);
+        (t0, t1) = (t1, t0 - q * t1);
+    }
+    if r0 < 0 { (-r0, -s0, -t0) } else { (r0, s0, t0) }
+}
+
+// solve() for buttons that move along the same line, which leaves a single
+// equation a * u + b * v = p along one axis. Its solutions are a = a0 + k * v
+// / g and b = b0 - k * u / g, and 3a + b changes linearly with k, so the
+// cheapest is at one end of the range of k that keeps the presses in bounds.
+fn solve_colinear(buttons: ((i128, i128), (i128, i128)), prize: (i128, i128), max_presses: Option<i128>) -> Option<i128> {
+    let ((x1, y1), (x2, y2)) = buttons;
+    let (u, v, p) = if (x1, x2) != (0, 0) { (x1, x2, prize.0) } else { (y1, y2, prize.1) };
+    if (u, v) == (0, 0) {
+        // Neither button moves the claw.
+        return Some(0);
+    }
+    let (g, s, t) = ext_gcd(u, v);
+    if p % g != 0 {
+        return Some(0);
+    }
+    let (a0, b0) = (s.checked_mul(p / g)?, t.checked_mul(p / g)?);
+    let (da, db) = (v / g, -(u / g));
+
+    // Each bound on the presses is c + k * d >= 0.
+    let mut bounds = vec![(a0, da), (b0, db)];
+    if let Some(max) = max_presses {
+        bounds.extend([(max - a0, -da), (max - b0, -db)]);
+    }
+    let (mut lo, mut hi): (Option<i128>, Option<i128>) = (None, None);
+    for (c, d) in bounds {
+        match d.cmp(&0) {
+            Ordering::Greater => lo = lo.max(Some(-floor_div(c, d))),
+            Ordering::Less => hi = Some(hi.map_or(floor_div(c, -d), |hi| hi.min(floor_div(c, -d)))),
+            Ordering::Equal if c < 0 => return Some(0),
+            Ordering::Equal => (),
+        }
+    }
+    if lo.zip(hi).is_some_and(|(lo, hi)| lo > hi) {
+        return Some(0);
+    }
+    // The cost can't fall forever, since it is never negative, so the end
+    // that it falls towards is always bounded.
+    let k = if 3 * da + db > 0 { lo } else { hi.or(lo) }?;
+    let a = a0.checked_add(k.checked_mul(da)?)?;
+    let b = b0.checked_add(k.checked_mul(db)?)?;
+    // The other axis agrees for every solution or none.
+    let fits = a.checked_mul(x1)?.checked_add(b.checked_mul(x2)?)? == prize.0
+        && a.checked_mul(y1)?.checked_add(b.checked_mul(y2)?)? == prize.1;
+    if fits { a.checked_mul(3)?.checked_add(b) } else { Some(0) }
+}
+
+// Adds the tokens for one more machine to `total`.
+fn add_tokens(total: i64, m: &Machine, part2: bool) -> error::Result<i64> {
+    solve(m, part2).and_then(|tokens| total.checked_add(tokens.try_into().ok()?))
+        .ok_or_else(|| error::Error::new(13, 0, 0, "a total that fits in an i64", "an overflow"))
+}
+
+pub fn solve_part1(machines: &Input) -> error::Result<i64> {
+    machines.iter().try_fold(0, |total, m| add_tokens(total, m, false))
+}
+
+pub fn solve_part2(machines: &Input) -> error::Result<i64> {
+    machines.iter().try_fold(0, |total, m| add_tokens(total, m, true))
+}
+
+// Reads the next non-empty line as one line of a machine, or returns None at
+// the end of the input.
+fn stream_pair<R: BufRead>(reader: &mut LineReader<R>, prefix: &str, sep: &str) -> error::Result<Option<(i64, i64)>> {
+    while let Some(line) = reader.next_line()? {
+        if !line.text.is_empty() {
+            return parse_pair(&line, prefix, sep).map(Some);
+        }
+    }
+    Ok(None)
+}
+
+// Solves one machine at a time, so only the current line is in memory.
+fn stream_total(input: impl BufRead, part2: bool) -> error::Result<i64> {
+    let mut reader = LineReader::new(13, input);
+    let mut total = 0;
+    while let Some(a) = stream_pair(&mut reader, "Button A: ", "+")? {
+        let b = stream_pair(&mut reader, "Button B: ", "+")?.ok_or_else(|| reader.end_of_input("`Button B: `"))?;
+        let prize = stream_pair(&mut reader, "Prize: ", "=")?.ok_or_else(|| reader.end_of_input("`Prize: `"))?;
+        total = add_tokens(total, &Machine { a, b, prize }, part2)?;
+    }
+    Ok(total)
+}
+
+pub fn stream_part1(input: impl BufRead) -> error::Result<i64> {
+    stream_total(input, false)
+}
+
+pub fn stream_part2(input: impl BufRead) -> error::Result<i64> {
+    stream_total(input, true)
+}
+
+// A straightforward version of get_total(). Part 1 tries every number of
+// presses up to 100, and part 2 solves the equations in 128-bit arithmetic.
+// This is what the fast path is checked against in crate::diff.
+pub(crate) fn reference_total(input: &str, part2: bool) -> Option<i64> {
+    let machines = parse(input).ok()?;
+    machines.iter().map(|m| {
+        let ((x1, y1), (x2, y2), (x3, y3)) = (m.a, m.b, m.prize);
+        if !part2 {
+            let presses = (0..=100).flat_map(|a| (0..=100).map(move |b| (a, b)));
+            return Some(presses.filter(|(a, b)| a * x1 + b * x2 == x3 && a * y1 + b * y2 == y3)
+                .map(|(a, b)| 3 * a + b)
+                .min()
+                .unwrap_or(0));
+        }
+        let ((x1, y1), (x2, y2)) = ((x1 as i128, y1 as i128), (x2 as i128, y2 as i128));
+        let (x3, y3) = (x3 as i128 + 10000000000000, y3 as i128 + 10000000000000);
+        let det = x1 * y2 - x2 * y1;
+        if det == 0 {
+            // The buttons move along the same line, and the A presses that
+            // reach the prize are `step` apart. Only buttons that move up and
+            // right are handled, and the cheapest is the fewest A presses or
+            // the most.
+            if [x1, y1, x2, y2].iter().any(|&n| n <= 0) {
+                return None;
+            }
+            let Some(fewest) = (0..=x2).find(|a| a * x1 <= x3 && (x3 - a * x1) % x2 == 0) else {
+                return Some(0);
+            };
+            let step = (1..=x2).find(|s| s * x1 % x2 == 0).unwrap();
+            let most = fewest + (x3 / x1 - fewest) / step * step;
+            return Some([fewest, most].into_iter()
+                .map(|a| (a, (x3 - a * x1) / x2))
+                .filter(|&(a, b)| a * y1 + b * y2 == y3)
+                .map(|(a, b)| (3 * a + b) as i64)
+                .min()
+                .unwrap_or(0));
+        }
+        let (na, nb) = (x3 * y2 - x2 * y3, x1 * y3 - x3 * y1);
+        if na % det != 0 || nb % det != 0 || na / det < 0 || nb / det < 0 {
+            return Some(0);
+        }
+        Some((3 * (na / det) + nb / det) as i64)
+    }).sum()
+}
+
+pub fn try_part1(input: &str) -> error::Result<i64> {
+    solve_part1(&parse(input)?)
+}
+
+pub fn try_part2(input: &str) -> error::Result<i64> {
+    solve_part2(&parse(input)?)
+}
+
+#[aoc(day13, part1)]
+pub fn part1(input: &str) -> i64 {
+    match fits_fast_path(input).then(|| unsafe {get_total(input, false)}).flatten() {
+        Some(total) => total,
+        None => try_part1(input).unwrap(),
+    }
+}
+
+#[aoc(day13, part2)]
+pub fn part2(input: &str) -> i64 {
+    match fits_fast_path(input).then(|| unsafe {get_total(input, true)}).flatten() {
+        Some(total) => total,
+        None => try_part2(input).unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answer::Answer;
+    use crate::manifest::expected;
+
+    static TEST_INPUT: &str = include_str!("../../examples/2024/day13/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Some(480), reference_total(TEST_INPUT, false));
+        assert_eq!(Ok(480), try_part1(TEST_INPUT));
+        assert_eq!(Ok(480), stream_part1(TEST_INPUT.as_bytes()));
+        assert_eq!("day 13, line 3: expected `Prize: `, found end of input",
+            stream_part1("Button A: X+1, Y+2\nButton B: X+2, Y+1\n".as_bytes()).unwrap_err().to_string());
+        assert_eq!("day 13, line 2, column 13: expected a number, found `2x`",
+            try_part1("Button A: X+1, Y+2\nButton B: X+2x, Y+1\nPrize: X=1, Y=1").unwrap_err().to_string());
+
+        assert_eq!(expected(13, 1), part1(include_str!("../../input/2024/day13.txt")).into());
+
+        // Each button can be pressed at most 100 times in part 1.
+        let input = "Button A: X+10, Y+20\nButton B: X+20, Y+10\nPrize: X=3030, Y=3030\n";
+        assert_eq!((0, Ok(0)), (part1(input), try_part1(input)));
+    }
+
+    #[test]
+    fn test_fast_path() {
+        assert!(fits_fast_path(TEST_INPUT));
+        assert!(fits_fast_path(include_str!("../../input/2024/day13.txt")));
+        assert!(fits_fast_path(TEST_INPUT.trim_end()));
+        assert!(!fits_fast_path("Button A: X+94, Y+34\nButton B: X+22"));
+        assert!(!fits_fast_path("Button A: X+94, Y+34\nButton B: X+94, Y+34\nPrize: X=1, Y=1"));
+        assert!(!fits_fast_path("Button A: X+94, Y+-34\nButton B: X+22, Y+67\nPrize: X=1, Y=1"));
+
+        assert!(!fits_fast_path("Button A: X+123456, Y+34\nButton B: X+22, Y+67\nPrize: X=1, Y=1"));
+
+        // Input that doesn't fit goes to the safe parser.
+        assert_eq!(20, part1("Button A: X+1, Y+1\nButton B: X+1, Y+2\nPrize: X=+20, Y=40"));
+        let long = "Button A: X+123456789, Y+2\nButton B: X+3, Y+987654321\nPrize: X=123456792, Y=987654323";
+        assert_eq!(4, part1(long));
+        assert_eq!(reference_total(long, true), Some(part2(long)));
+
+        // Buttons that move along the same line.
+        let colinear = "Button A: X+2, Y+2\nButton B: X+1, Y+1\nPrize: X=5, Y=5";
+        assert_eq!((5, 5), (part1(colinear), stream_part1(colinear.as_bytes()).unwrap()));
+        assert_eq!(10000000000005, part2(colinear));
+        assert_eq!(Some(10000000000005), reference_total(colinear, true));
+        assert_eq!(5, part1("Button A: X+3, Y+6\nButton B: X+2, Y+4\nPrize: X=7, Y=14"));
+        assert_eq!(0, part1("Button A: X+2, Y+2\nButton B: X+4, Y+4\nPrize: X=5, Y=5"));
+        assert_eq!(0, part1("Button A: X+2, Y+2\nButton B: X+1, Y+1\nPrize: X=5, Y=6"));
+        assert_eq!(0, part1("Button A: X+2, Y+2\nButton B: X+1, Y+1\nPrize: X=500, Y=500"));
+        assert_eq!(0, part1("Button A: X+0, Y+0\nButton B: X+0, Y+0\nPrize: X=1, Y=1"));
+        assert_eq!(2, part1("Button A: X+0, Y+2\nButton B: X+0, Y+3\nPrize: X=0, Y=6"));
+        assert_eq!("day 13: expected a total that fits in an i64, found an overflow",
+            try_part2("Button A: X+1, Y+0\nButton B: X+0, Y+1\nPrize: X=9223372036854775807, Y=9223372036854775807").unwrap_err().to_string());
+    }
+
+    #[cfg(any(feature = "checked", debug_assertions))]
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_checked() {
+        unsafe { get_total("Button A: X+94", false) };
+    }
+
+    #[test]
+    fn test_part2() {
+        // assert_eq!(0, part2(TEST_INPUT));
+
+        assert_eq!(expected(13, 2), part2(include_str!("../../input/2024/day13.txt")).into());
+        assert_eq!(expected(13, 2), stream_part2(include_bytes!("../../input/2024/day13.txt").as_slice()).unwrap().into());
+        assert_eq!(Some(expected(13, 2)), reference_total(include_str!("../../input/2024/day13.txt"), true).map(Answer::from));
+        assert_eq!(Ok(expected(13, 2)), try_part2(include_str!("../../input/2024/day13.txt")).map(Answer::from));
+    }
+}
@@ -0,0 +1,469 @@
+
+// https://adventofcode.com/2024/day/14
+
+use crate::error::{self, lines};
+use crate::grid::Grid;
+use crate::registry::Params;
+use crate::trace::{self, Event};
+use crate::unchecked::{assume, get, get_mut};
+use crate::viz;
+
+/// A robot's position and velocity: (px, py, vx, vy).
+pub type Robot = (i32, i32, i32, i32);
+
+pub type Input = Vec<Robot>;
+
+// Parse lines like "p=56,82 v=-79,-40", checking that every robot starts inside
+// an xb by yb space.
+pub(crate) fn parse_robots(input: &str, xb: i32, yb: i32) -> error::Result<Vec<Robot>> {
+    let mut robots: Vec<Robot> = Vec::with_capacity(512);
+    for line in lines(14, input).filter(|line| !line.text.is_empty()) {
+        let rest = line.expect(line.text, "p=")?;
+        let (px, rest) = line.split_once(rest, ",")?;
+        let (py, rest) = line.split_once(rest, " v=")?;
+        let (vx, vy) = line.split_once(rest, ",")?;
+        let px: i32 = line.parse(px, "an x position")?;
+        let py: i32 = line.parse(py, "a y position")?;
+        if !(0..xb).contains(&px) || !(0..yb).contains(&py) {
+            return Err(line.error_line(format!("a position within {}x{}", xb, yb)));
+        }
+        robots.push((px, py, line.parse(vx, "an x velocity")?, line.parse(vy, "a y velocity")?));
+    }
+    Ok(robots)
+}
+
+#[inline]
+#[allow(clippy::too_many_arguments)]
+unsafe fn process_line(input: &[u8], line_starts: &[usize], xb: i32, yb: i32, xbd2: i32, ybd2: i32, i: usize, checked: bool) -> u64 {
+    let line_start = get(line_starts, i);
+    // Parse something like this: p=56,82 v=-79,-40
+    let px_pos: usize = line_start + 2;
+    let px_byte_0 = *get(input, px_pos  ) as i32 - b'0' as i32;
+    let px_byte_1 = *get(input, px_pos+1) as i32 - b'0' as i32;
+    let px_byte_2 = *get(input, px_pos+2) as i32 - b'0' as i32;
+    let px_if_1 = px_byte_0;
+    let px_if_2 = px_byte_1 + 10 * px_if_1;
+    let px_if_3 = px_byte_2 + 10 * px_if_2;
+    let py_pos_if_1 = px_pos + 2;
+    let py_pos_if_2 = px_pos + 3;
+    let py_pos_if_3 = px_pos + 4;
+    let (px, py_pos) =
+        if px_byte_1 < 0 {
+            (px_if_1, py_pos_if_1)
+        } else if px_byte_2 < 0 {
+            (px_if_2, py_pos_if_2)
+        } else {
+            (px_if_3, py_pos_if_3)
+        };
+
+    let py_byte_0 = *get(input, py_pos  ) as i32 - b'0' as i32;
+    let py_byte_1 = *get(input, py_pos+1) as i32 - b'0' as i32;
+    let py_byte_2 = *get(input, py_pos+2) as i32 - b'0' as i32;
+    let py_if_1 = py_byte_0;
+    let py_if_2 = py_byte_1 + 10 * py_if_1;
+    let py_if_3 = py_byte_2 + 10 * py_if_2;
+    let vx_pos_if_1 = py_pos + 4;
+    let vx_pos_if_2 = py_pos + 5;
+    let vx_pos_if_3 = py_pos + 6;
+    let (py, vx_pos) =
+        if py_byte_1 < 0 {
+            (py_if_1, vx_pos_if_1)
+        } else if py_byte_2 < 0 {
+            (py_if_2, vx_pos_if_2)
+        } else {
+            (py_if_3, vx_pos_if_3)
+        };
+
+    let vx_pos_3 = if checked && vx_pos+3 >= input.len() { py_pos-1 } else { vx_pos+3 };
+    let vx_byte_0 = *get(input, vx_pos  ) as i32 - b'0' as i32;
+    let vx_byte_1 = *get(input, vx_pos+1) as i32 - b'0' as i32;
+    let vx_byte_2 = *get(input, vx_pos+2) as i32 - b'0' as i32;
+    let vx_byte_3 = *get(input, vx_pos_3) as i32 - b'0' as i32;
+    let vx_if_1  = vx_byte_0;
+    let vx_if_2  = vx_byte_1 + 10 * vx_if_1;
+    let vx_if_3  = vx_byte_2 + 10 * vx_if_2;
+    let vx_if_n1 = xb - vx_byte_1;
+    let vx_if_n2 = xb - vx_byte_2 + 10 * vx_if_n1;
+    let vx_if_n3 = xb - vx_byte_3 + 10 * vx_if_n2;
+    let vy_pos_if_1  = vx_pos + 2;
+    let vy_pos_if_2  = vx_pos + 3;
+    let vy_pos_if_3  = vx_pos + 4;
+    let vy_pos_if_n1 = vx_pos + 3;
+    let vy_pos_if_n2 = vx_pos + 4;
+    let vy_pos_if_n3 = vx_pos + 5;
+    let (vx, vy_pos) =
+        if vx_byte_0 < 0 { // negative
+            if vx_byte_2 < 0 {
+                (vx_if_n1, vy_pos_if_n1)
+            } else if vx_byte_3 < 0 {
+                (vx_if_n2, vy_pos_if_n2)
+            } else {
+                (vx_if_n3, vy_pos_if_n3)
+            }
+        } else { // positive
+            if vx_byte_1 < 0 {
+                (vx_if_1, vy_pos_if_1)
+            } else if vx_byte_2 < 0 {
+                (vx_if_2, vy_pos_if_2)
+            } else {
+                (vx_if_3, vy_pos_if_3)
+            }
+        };
+
+    let vy_pos_1 = if checked && vy_pos+1 >= input.len() { py_pos-1 } else { vy_pos+1 };
+    let vy_pos_2 = if checked && vy_pos+2 >= input.len() { py_pos-1 } else { vy_pos+2 };
+    let vy_pos_3 = if checked && vy_pos+3 >= input.len() { py_pos-1 } else { vy_pos+3 };
+    let vy_byte_0 = *get(input, vy_pos  ) as i32 - b'0' as i32;
+    let vy_byte_1 = *get(input, vy_pos_1) as i32 - b'0' as i32;
+    let vy_byte_2 = *get(input, vy_pos_2) as i32 - b'0' as i32;
+    let vy_byte_3 = *get(input, vy_pos_3) as i32 - b'0' as i32;
+    let vy_if_1  = vy_byte_0;
+    let vy_if_2  = vy_byte_1 + 10 * vy_if_1;
+    let vy_if_3  = vy_byte_2 + 10 * vy_if_2;
+    let vy_if_n1 = yb - vy_byte_1;
+    let vy_if_n2 = yb - vy_byte_2 + 10 * vy_if_n1;
+    let vy_if_n3 = yb - vy_byte_3 + 10 * vy_if_n2;
+    let vy =
+        if vy_byte_0 < 0 { // negative
+            if vy_byte_2 < 0 {
+                vy_if_n1
+            } else if vy_byte_3 < 0 {
+                vy_if_n2
+            } else {
+                vy_if_n3
+            }
+        } else { // positive
+            if vy_byte_1 < 0 {
+                vy_if_1
+            } else if vy_byte_2 < 0 {
+                vy_if_2
+            } else {
+                vy_if_3
+            }
+        };
+
+    // Parsing is done. Now use (px, py, vx, vy) to calculate the final position
+    // (npx, npy)
+
+    let npx = px + (vx * 100);
+    let npy = py + (vy * 100);
+    assume(npx >= 0);
+    assume(npy >= 0);
+    assume(xb > 0);
+    assume(yb > 0);
+    let npx = npx % xb;
+    let npy = npy % yb;
+
+    // Set 1 in only one quadrant.
+    let q1: u64 = if npx < xbd2 && npy < ybd2 { 1 } else { 0 };
+    let q2: u64 = if npx > xbd2 && npy < ybd2 { 1 } else { 0 };
+    let q3: u64 = if npx < xbd2 && npy > ybd2 { 1 } else { 0 };
+    let q4: u64 = if npx > xbd2 && npy > ybd2 { 1 } else { 0 };
+
+    // Store the quadrant selection in a u64 for efficiency. These can be summed
+    // up without fear that values will roll over into another quadrant because
+    // the total bots is ~500, well below 2^16 to cause a rollover.
+    (q1 << 48) + (q2 << 32) + (q3 << 16) + q4
+}
+
+unsafe fn quad_prod_vec(input: &str, xb: i32, yb: i32) -> u64 {
+    let input = input.as_bytes();
+    let xbd2 = xb / 2;
+    let ybd2 = yb / 2;
+    // Lines are at least 12 bytes each.
+    let mut line_starts: Vec<usize> = vec![0; input.len()/12];
+    let line_starts: &mut [usize] = line_starts.as_mut_slice();
+    let mut count = 0;
+    let mut i = 0;
+    while i < input.len() {
+        if input[i] == b'p' {
+            *get_mut(line_starts, count) = i;
+            count += 1;
+            i += 12; // 12 is the shortest possible line length.
+        } else {
+            i += 1;
+        }
+    }
+    let line_starts: &[usize] = line_starts;
+    let count = count;
+
+    // Candidate for autovectorization.
+    let total_64: u64 = (0..count-1).map(|i| {
+        let checked = false;
+        process_line(input, line_starts, xb, yb, xbd2, ybd2, i, checked)
+    }).sum();
+    let total_64 = total_64 + process_line(input, line_starts, xb, yb, xbd2, ybd2, count-1, true);
+
+    // Get separate values for the 4 quadrants and multiply them together.
+    ((total_64 >> 48) & 0xffff) *
+        ((total_64 >> 32) & 0xffff) *
+        ((total_64 >> 16) & 0xffff) *
+        ((total_64      ) & 0xffff)
+}
+
+// Reads a number of 1 to 3 digits from the start of `s`, with an optional `-`
+// if `signed`.
+fn fast_number(s: &[u8], signed: bool) -> Option<(i32, &[u8])> {
+    let (sign, s) = match s.strip_prefix(b"-") {
+        Some(rest) if signed => (-1, rest),
+        _ => (1, s),
+    };
+    let len = s.iter().take_while(|b| b.is_ascii_digit()).count();
+    if !(1..=3).contains(&len) {
+        return None;
+    }
+    Some((sign * s[..len].iter().fold(0, |n, b| 10 * n + (b - b'0') as i32), &s[len..]))
+}
+
+fn fast_robot(line: &str) -> Option<Robot> {
+    let (px, rest) = fast_number(line.as_bytes().strip_prefix(b"p=")?, false)?;
+    let (py, rest) = fast_number(rest.strip_prefix(b",")?, false)?;
+    let (vx, rest) = fast_number(rest.strip_prefix(b" v=")?, true)?;
+    let (vy, rest) = fast_number(rest.strip_prefix(b",")?, true)?;
+    rest.is_empty().then_some((px, py, vx, vy))
+}
+
+// Checks everything that quad_prod_vec() assumes about its input without
+// checking: there is at least one robot, every line is exactly "p=X,Y v=X,Y"
+// with short enough numbers and positions inside the space, velocities are
+// slower than one lap per step, and the lines average at least 12 bytes.
+fn fits_fast_path(input: &str, xb: i32, yb: i32) -> bool {
+    let mut count = 0;
+    for line in input.lines().filter(|line| !line.is_empty()) {
+        match fast_robot(line) {
+            Some((px, py, vx, vy)) if px < xb && py < yb && vx.abs() < xb && vy.abs() < yb => count += 1,
+            _ => return false,
+        }
+    }
+    count > 0 && count * 12 <= input.len()
+}
+
+fn quad_prod_vec_slow(input: &str, xb: i32, yb: i32) -> u64 {
+    quad_prod(&parse_robots(input, xb, yb).unwrap(), xb, yb)
+}
+
+fn quad_prod(robots: &[Robot], xb: i32, yb: i32) -> u64 {
+    let xbd2 = xb / 2;
+    let ybd2 = yb / 2;
+    let total_64: u64 = robots.iter().map(|&(px, py, vx, vy)| {
+        // Use (px, py, vx, vy) to calculate the final position (npx, npy)
+
+        let npx = (px + (vx * 100)) % xb;
+        let npx_pxb = npx + xb;
+        let npx = if npx < 0 { npx_pxb } else { npx };
+        let npy = (py + (vy * 100)) % yb;
+        let npy_pyb = npy + yb;
+        let npy = if npy < 0 { npy_pyb } else { npy };
+
+        let q1: u64 = if npx < xbd2 && npy < ybd2 { 1 } else { 0 };
+        let q2: u64 = if npx > xbd2 && npy < ybd2 { 1 } else { 0 };
+        let q3: u64 = if npx < xbd2 && npy > ybd2 { 1 } else { 0 };
+        let q4: u64 = if npx > xbd2 && npy > ybd2 { 1 } else { 0 };
+
+        // Store the quadrant selection in a u64 for efficiency. These can be
+        // summed up without fear that values will roll over into another
+        // quadrant because the total bots is ~500, well below 2^16 to cause a
+        // rollover.
+        (q1 << 48) + (q2 << 32) + (q3 << 16) + q4
+    }).sum();
+
+    // Get separate values for the 4 quadrants and multiply them together.
+    ((total_64 >> 48) & 0xffff) *
+        ((total_64 >> 32) & 0xffff) *
+        ((total_64 >> 16) & 0xffff) *
+        ((total_64      ) & 0xffff)
+}
+
+/// The robots in the real 101 by 103 space.
+pub fn parse(input: &str) -> error::Result<Input> {
+    parse_robots(input, 101, 103)
+}
+
+pub fn solve_part1(robots: &Input) -> u64 {
+    quad_prod(robots, 101, 103)
+}
+
+pub fn try_part1(input: &str) -> error::Result<u64> {
+    Ok(solve_part1(&parse(input)?))
+}
+
+#[aoc(day14, part1)]
+pub fn part1(input: &str) -> u64 {
+    if !fits_fast_path(input, 101, 103) {
+        return quad_prod_vec_slow(input, 101, 103);
+    }
+    let res = unsafe { quad_prod_vec(input, 101, 103) };
+    debug_assert_eq!(res, quad_prod_vec_slow(input, 101, 103));
+    res
+}
+
+// The two implementations that part1 checks against each other, exposed
+// separately so that they can be compared.
+#[aoc(day14, part1, QuadProdVec)]
+pub fn part1_quad_prod_vec(input: &str) -> u64 {
+    if !fits_fast_path(input, 101, 103) {
+        return quad_prod_vec_slow(input, 101, 103);
+    }
+    unsafe { quad_prod_vec(input, 101, 103) }
+}
+
+#[aoc(day14, part1, QuadProdVecSlow)]
+pub fn part1_quad_prod_vec_slow(input: &str) -> u64 {
+    quad_prod_vec_slow(input, 101, 103)
+}
+
+pub fn solve_part2(robots: &Input) -> u64 {
+    tree_seconds(robots, 101, 103)
+}
+
+// How many seconds until the robots in an xb by yb space form the tree.
+fn tree_seconds(robots: &[Robot], xb: i32, yb: i32) -> u64 {
+    let mut bots = robots.to_vec();
+    for bot in bots.iter_mut() {
+        // Keep the velocities positive so that the positions stay positive.
+        bot.2 = bot.2.rem_euclid(xb);
+        bot.3 = bot.3.rem_euclid(yb);
+    }
+    let mut count: u64 = 0;
+    let mut printed = false;
+    while !printed {
+        printed = true;
+        let mut map: Vec<Vec<char>> = vec![vec!['.'; xb as usize]; yb as usize];
+        for bot in bots.iter_mut() {
+            let m = map.get_mut(bot.1 as usize).unwrap().get_mut(bot.0 as usize).unwrap();
+            // https://www.reddit.com/r/adventofcode/comments/1he88a8/
+            if *m == '#' {
+                printed = false;
+            }
+            *m = '#';
+            bot.0 = (bot.0 + bot.2) % xb;
+            bot.1 = (bot.1 + bot.3) % yb;
+        }
+
+        // https://www.reddit.com/r/adventofcode/comments/1he88a8/comment/m229vlf/
+        if printed {
+            printed = false;
+            let mut consecutive = 0;
+            for l in map.iter() {
+                for c in l {
+                    if *c == '.' {
+                        consecutive = 0;
+                    } else if consecutive > 8 {
+                        printed = true;
+                    } else {
+                        consecutive += 1;
+                    }
+                }
+                consecutive = 0;
+            }
+
+        }
+        if printed {
+            // Run with AOC24_TRACE=1 to see the tree.
+            trace::emit(|| Event::Grid {
+                source: "day14",
+                label: "tree",
+                step: count,
+                grid: map.iter().map(|l| l.iter().collect::<String>() + "\n").collect(),
+            });
+        } else {
+            count += 1;
+        }
+    }
+    trace::emit(|| Event::Iterations { source: "day14", label: "seconds", count: count + 1 });
+    count
+}
+
+pub fn try_part2(input: &str) -> error::Result<u64> {
+    Ok(solve_part2(&parse(input)?))
+}
+
+/// `width` and `height` override the size of the space.
+pub const PARAMS: &[&str] = &["width", "height"];
+
+pub fn try_part1_with(input: &str, params: &Params) -> error::Result<u64> {
+    let (xb, yb) = (params.get(14, "width", 101)?, params.get(14, "height", 103)?);
+    Ok(quad_prod(&parse_robots(input, xb, yb)?, xb, yb))
+}
+
+pub fn try_part2_with(input: &str, params: &Params) -> error::Result<u64> {
+    let (xb, yb) = (params.get(14, "width", 101)?, params.get(14, "height", 103)?);
+    Ok(tree_seconds(&parse_robots(input, xb, yb)?, xb, yb))
+}
+
+/// Records the robots each second until they form the Christmas tree, which
+/// the last frame highlights.
+pub fn record(input: &str, rec: &mut viz::Recorder) -> error::Result<()> {
+    let (xb, yb) = (101, 103);
+    let robots = parse(input)?;
+    let tree = solve_part2(&robots);
+    let frame = |second: u64, color: u8| {
+        let mut frame = Grid::new(xb as usize, yb as usize, viz::EMPTY);
+        for &(px, py, vx, vy) in &robots {
+            let x = (px as i64 + vx as i64 * second as i64).rem_euclid(xb as i64);
+            let y = (py as i64 + vy as i64 * second as i64).rem_euclid(yb as i64);
+            frame[(y as usize, x as usize)] = color;
+        }
+        frame
+    };
+    for second in 0..tree {
+        rec.step(|| frame(second, viz::ACTOR));
+    }
+    rec.finish(frame(tree, viz::HIGHLIGHT));
+    Ok(())
+}
+
+#[aoc(day14, part2)]
+pub fn part2(input: &str) -> u64 {
+    try_part2(input).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::expected;
+
+    static TEST_INPUT: &str = include_str!("../../examples/2024/day14/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(12, unsafe { quad_prod_vec(TEST_INPUT, 11, 7) } );
+        assert_eq!(12, quad_prod_vec_slow(TEST_INPUT, 11, 7));
+        assert_eq!("day 14, line 1, column 9: expected ` v=`, found end of line",
+            try_part1("p=1,2 v3").unwrap_err().to_string());
+
+        assert_eq!(expected(14, 1), part1(include_str!("../../input/2024/day14.txt")).into());
+        assert_eq!(expected(14, 1), part1_quad_prod_vec(include_str!("../../input/2024/day14.txt")).into());
+        assert_eq!(expected(14, 1), part1_quad_prod_vec_slow(include_str!("../../input/2024/day14.txt")).into());
+    }
+
+    #[test]
+    fn test_fast_path() {
+        assert!(fits_fast_path(TEST_INPUT, 11, 7));
+        assert!(fits_fast_path(include_str!("../../input/2024/day14.txt"), 101, 103));
+        assert!(!fits_fast_path("", 11, 7));
+        assert!(!fits_fast_path("p=1,2 v=3,4", 11, 7));
+        assert!(!fits_fast_path("p=1,2 v=3,4\np=1000,2 v=3,4\n", 1001, 7));
+        assert!(!fits_fast_path("p=1,2 v=3,4\np=1,2 v=3,-7\n", 11, 7));
+        assert!(!fits_fast_path("p=1,2 v=3,4\np=1,2 v=3,+4\n", 11, 7));
+
+        // Input that doesn't fit goes to the safe parser.
+        assert_eq!(0, part1("p=1,2 v=3,4"));
+        assert_eq!(0, part1_quad_prod_vec("p=1,2 v=3,4"));
+    }
+
+    #[cfg(any(feature = "checked", debug_assertions))]
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_checked() {
+        // A single line too short to have room for its own line start.
+        unsafe { quad_prod_vec("p=1,2 v=3,4", 11, 7) };
+    }
+
+    #[test]
+    fn test_part2() {
+        // assert_eq!(0, part2(TEST_INPUT));
+
+        assert_eq!(expected(14, 2), part2(include_str!("../../input/2024/day14.txt")).into());
+    }
+}
@@ -0,0 +1,360 @@
+
+// https://adventofcode.com/2024/day/15
+
+use crate::error::{self, lines};
+use crate::grid::{Direction, Grid, GridCell, Pos};
+use crate::trace::{self, Event};
+use crate::viz;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum Cell {
+    Robot,
+    Empty,
+    Wall,
+    MovableBox,
+    LeftMovableBox,
+    RightMovableBox,
+}
+
+impl GridCell for Cell {
+    fn from_byte(b: u8) -> Self {
+        Cell::parse(b).expect("Invalid cell")
+    }
+
+    fn to_byte(&self) -> u8 {
+        match *self {
+            Cell::MovableBox => b'O',
+            Cell::LeftMovableBox => b'[',
+            Cell::RightMovableBox => b']',
+            Cell::Empty => b'.',
+            Cell::Wall => b'#',
+            Cell::Robot => b'@',
+        }
+    }
+}
+
+impl Cell {
+    fn parse(b: u8) -> Option<Self> {
+        match b {
+            b'.' => Some(Cell::Empty),
+            b'#' => Some(Cell::Wall),
+            b'O' => Some(Cell::MovableBox),
+            b'@' => Some(Cell::Robot),
+            _ => None,
+        }
+    }
+
+    // The left half of this cell on a double-wide map.
+    fn left(&self) -> Self {
+        match *self {
+            Cell::MovableBox => Cell::LeftMovableBox,
+            c => c,
+        }
+    }
+
+    // The right half of this cell on a double-wide map.
+    fn right(&self) -> Self {
+        match *self {
+            Cell::MovableBox => Cell::RightMovableBox,
+            Cell::Robot => Cell::Empty,
+            c => c,
+        }
+    }
+}
+
+/// The map of the warehouse, and where the robot is on it.
+#[derive(Clone)]
+pub struct Warehouse {
+    cells: Grid<Cell>,
+    robot_pos: Pos,
+}
+
+impl Warehouse {
+    fn from(map: &str) -> error::Result<Self> {
+        let mut robots = 0;
+        let cells: Grid<Cell> = Grid::try_parse_with(15, map, |_, b| {
+            let cell = Cell::parse(b).ok_or("one of `.`, `#`, `O` or `@`")?;
+            if cell == Cell::Robot {
+                robots += 1;
+            }
+            Ok(cell)
+        })?;
+        if robots != 1 {
+            return Err(error::Error::new(15, 0, 0, "exactly one robot (`@`)", robots.to_string()));
+        }
+        // The robot and boxes move without bounds checks, so they must be
+        // fenced in.
+        for (pos, cell) in cells.iter() {
+            let edge = pos.0 == 0 || pos.1 == 0 || pos.0 == cells.height() - 1 || pos.1 == cells.width() - 1;
+            if edge && *cell != Cell::Wall {
+                return Err(error::Error::new(15, pos.0 + 1, pos.1 + 1, "a wall (`#`) around the edge",
+                    format!("`{}`", cell.to_byte() as char)));
+            }
+        }
+        let robot_pos = cells.position(|c| *c == Cell::Robot).unwrap();
+        Ok(Warehouse {
+            cells,
+            robot_pos,
+        })
+    }
+
+    // The double-wide version of this warehouse, for part 2.
+    fn widen(&self) -> Self {
+        let cells = Grid::from_fn(self.cells.width() * 2, self.cells.height(), |(row, col)| {
+            let cell = self.cells[(row, col / 2)];
+            if col % 2 == 0 { cell.left() } else { cell.right() }
+        });
+        Warehouse { cells, robot_pos: (self.robot_pos.0, self.robot_pos.1 * 2) }
+    }
+
+    // This is the core function that moves the robot and boxes around the grid.
+    fn push_from(&mut self, pos: &mut Pos, dir: &Direction, dry_run: bool) -> bool {
+        match self.cells[*pos] {
+            Cell::Empty => {
+                return true;
+            },
+            Cell::Wall => {
+                // A dry run is always tried first. If the dry run failed (a
+                // wall was hit), an actual run would not be attempted.
+                debug_assert!(dry_run);
+                return false;
+            },
+            Cell::LeftMovableBox => {
+                debug_assert_eq!(self.cells[(pos.0, pos.1+1)], Cell::RightMovableBox);
+            },
+            Cell::RightMovableBox => {
+                debug_assert_eq!(self.cells[(pos.0, pos.1-1)], Cell::LeftMovableBox);
+            },
+            _ => {},
+        }
+        let new_pos = dir.advance(*pos);
+        let mut movable = true;
+        
+        let mut try_pos = new_pos;
+        if !self.push_from(&mut try_pos, dir, dry_run) {
+            movable = false;
+        }
+        
+        // Move the second half of a box
+        match (self.cells[*pos], self.cells[new_pos], dir.is_vertical()) {
+            (Cell::LeftMovableBox, Cell::LeftMovableBox, true) | (Cell::RightMovableBox, Cell::RightMovableBox, true) => {
+                // The displaced box is aligned with this one, so it was already moved. This is a no-op.
+                // Consider if we did not skip this push during a dry run...
+                // - The displaced box isn't actually moved because it is a dry run, so it will still be in its original place at this point.
+                // - We will check if we can move the displaced box a second time.
+                // - If there is a stack of N aligned boxes...
+                //   - The first box will be checked once,
+                //   - the second box will be checked twice,
+                //   - the third box will be checked four times...
+                //   - There will be 2^N-1 total calls! 
+                //
+                // This is imperfect though...
+                // Consider the stack:
+                //     v
+                //     []
+                //    [][]
+                //     []
+                // The bottom box will be checked twice even with this
+                // optimization, once as a result of each box above it.
+                // We could add a flag to track all of the checked boxes, but
+                // then we would have to walk the tree a second time just to
+                // clear all of the flags. This would have better worst-case
+                // performance, but it would likely have worse average-case
+                // performance, so it is not implemnted.
+                //
+                // TODO: Consider implementing this optimization to try if there
+                // is a perf issue.
+            }
+            (Cell::LeftMovableBox, _, true) => {
+                let mut new_right_pos = new_pos;
+                new_right_pos.1 += 1;
+                if !self.push_from(&mut new_right_pos, dir, dry_run) {
+                    movable = false;
+                }
+            },
+            (Cell::RightMovableBox, _, true) => {
+                let mut new_left_pos = new_pos;
+                new_left_pos.1 -= 1;
+                if !self.push_from(&mut new_left_pos, dir, dry_run) {
+                    movable = false;
+                }
+            },
+            _ => {}, // A few panic cases
+        }
+
+        if dry_run {
+            return movable;
+        }
+        if !movable {
+            panic!("Not a dry run, but cannot move.")
+        }
+
+        // Move the box/robot
+        match self.cells[*pos] {
+            Cell::MovableBox | Cell::Robot => {
+                debug_assert_eq!(self.cells[new_pos], Cell::Empty);
+                self.cells[new_pos] = self.cells[*pos];
+                self.cells[*pos] = Cell::Empty;
+                *pos = new_pos;
+            },
+            Cell::LeftMovableBox => {
+                debug_assert_eq!(self.cells[new_pos], Cell::Empty);
+                self.cells[new_pos] = Cell::LeftMovableBox;
+                self.cells[*pos] = Cell::Empty;
+                if dir.is_vertical() {
+                    debug_assert_eq!(self.cells[(new_pos.0, new_pos.1+1)], Cell::Empty);
+                    self.cells[(new_pos.0, new_pos.1+1)] = Cell::RightMovableBox;
+                    self.cells[(pos.0, pos.1+1)] = Cell::Empty;
+                }
+                *pos = new_pos;
+            },
+            Cell::RightMovableBox => {
+                debug_assert_eq!(self.cells[new_pos], Cell::Empty);
+                self.cells[new_pos] = Cell::RightMovableBox;
+                self.cells[*pos] = Cell::Empty;
+                if dir.is_vertical() {
+                    debug_assert_eq!(self.cells[(new_pos.0, new_pos.1-1)], Cell::Empty);
+                    self.cells[(new_pos.0, new_pos.1-1)] = Cell::LeftMovableBox;
+                    self.cells[(pos.0, pos.1-1)] = Cell::Empty;
+                }
+                *pos = new_pos;
+            },
+            Cell::Empty | Cell::Wall => {
+                panic!("Nothing to move, should have returned earlier");
+            },
+        }
+        true
+    }
+    fn push_robot(&mut self, dir: Direction) {
+        let mut robot_pos = self.robot_pos;
+        if self.push_from(&mut robot_pos, &dir, true) {
+            self.push_from(&mut robot_pos, &dir, false);
+        }
+        self.robot_pos = robot_pos;
+    }
+    fn frame(&self) -> viz::Frame {
+        self.cells.map(|cell| match cell {
+            Cell::Robot => viz::ACTOR,
+            Cell::Empty => viz::EMPTY,
+            Cell::Wall => viz::WALL,
+            Cell::MovableBox | Cell::LeftMovableBox | Cell::RightMovableBox => viz::BOX,
+        })
+    }
+    fn coordinate_sum(&self) -> u64 {
+        let mut sum = 0;
+        for ((i, j), cell) in self.cells.iter() {
+            match cell {
+                Cell::MovableBox | Cell::LeftMovableBox => { sum += i * 100 + j; },
+                _ => {},
+            };
+        }
+        sum as u64
+    }
+}
+
+impl std::fmt::Display for Warehouse {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.cells)
+    }
+}
+
+/// The warehouse as drawn, which part 2 widens, and the robot's moves.
+pub type Input = (Warehouse, Vec<Direction>);
+
+pub fn parse(input: &str) -> error::Result<Input> {
+    let (map, _) = input.split_once("\n\n")
+        .ok_or_else(|| error::end_of_input(15, input, "a blank line and a list of moves"))?;
+    let grid = Warehouse::from(map)?;
+    let mut moves = Vec::new();
+    for line in lines(15, input).skip(map.lines().count() + 1) {
+        for (i, b) in line.text.bytes().enumerate() {
+            let dir = Direction::from_arrow(b)
+                .ok_or_else(|| line.error(&line.text[i..], "one of `^`, `>`, `v` or `<`"))?;
+            moves.push(dir);
+        }
+    }
+    Ok((grid, moves))
+}
+
+fn run_robot(mut grid: Warehouse, moves: &[Direction]) -> u64 {
+    trace::emit(|| Event::Grid { source: "day15", label: "start", step: 0, grid: grid.to_string() });
+    for &dir in moves {
+        grid.push_robot(dir);
+    }
+    trace::emit(|| Event::Grid { source: "day15", label: "end", step: moves.len() as u64, grid: grid.to_string() });
+    grid.coordinate_sum()
+}
+
+/// Records the robot pushing boxes around the double-wide warehouse of part
+/// 2, one frame per move.
+pub fn record(input: &str, rec: &mut viz::Recorder) -> error::Result<()> {
+    let (grid, moves) = parse(input)?;
+    let mut grid = grid.widen();
+    for &dir in &moves {
+        rec.step(|| grid.frame());
+        grid.push_robot(dir);
+    }
+    rec.finish(grid.frame());
+    Ok(())
+}
+
+pub fn solve_part1((grid, moves): &Input) -> u64 {
+    run_robot(grid.clone(), moves)
+}
+
+pub fn solve_part2((grid, moves): &Input) -> u64 {
+    run_robot(grid.widen(), moves)
+}
+
+pub fn try_part1(input: &str) -> error::Result<u64> {
+    Ok(solve_part1(&parse(input)?))
+}
+
+pub fn try_part2(input: &str) -> error::Result<u64> {
+    Ok(solve_part2(&parse(input)?))
+}
+
+#[aoc(day15, part1)]
+pub fn part1(input: &str) -> u64 {
+    try_part1(input).unwrap()
+}
+
+#[aoc(day15, part2)]
+pub fn part2(input: &str) -> u64 {
+    try_part2(input).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::expected;
+
+    static TEST_INPUT: &str = include_str!("../../examples/2024/day15/b.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!("day 15, line 5, column 2: expected one of `^`, `>`, `v` or `<`, found `x`",
+            try_part1("###\n#@#\n###\n\n^x").unwrap_err().to_string());
+        assert_eq!("day 15, line 2, column 3: expected a wall (`#`) around the edge, found `.`",
+            try_part1("###\n#@.\n###\n\n^").unwrap_err().to_string());
+
+        assert_eq!(expected(15, 1), part1(include_str!("../../input/2024/day15.txt")).into());
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(expected(15, 2), part2(include_str!("../../input/2024/day15.txt")).into());
+    }
+
+    #[test]
+    fn test_record() {
+        let mut rec = viz::Recorder::new(1000);
+        record(TEST_INPUT, &mut rec).unwrap();
+        // A frame before each of the 700 moves, and one after the last.
+        assert_eq!(701, rec.frames().len());
+        let last = rec.frames().last().unwrap();
+        assert_eq!((20, 10), (last.width(), last.height()));
+        assert_eq!(Some((7, 4)), last.position(|&c| c == viz::ACTOR));
+        assert_eq!(21 * 2, last.iter().filter(|(_, &c)| c == viz::BOX).count());
+    }
+}
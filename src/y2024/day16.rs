@@ -0,0 +1,185 @@
+
+// https://adventofcode.com/2024/day/16
+
+use std::collections::HashSet;
+use crate::error;
+use crate::grid::{Direction, Grid, GridCell, Pos};
+use crate::search::{dijkstra, Search};
+use crate::trace::{self, Event};
+use crate::viz;
+
+// A position and the direction the reindeer is facing.
+type State = (Pos, Direction);
+
+/// The maze's walls, and the start and end tiles.
+pub struct Maze {
+    walls: Grid<bool>,
+    start_pos: Pos,
+    end_pos: Pos,
+}
+
+impl Maze {
+    fn from(input: &str) -> error::Result<Self> {
+        let mut start_pos = None;
+        let mut end_pos = None;
+        let walls = Grid::try_parse_with(16, input, |pos, b| {
+            match b {
+                b'S' => start_pos = Some(pos),
+                b'E' => end_pos = Some(pos),
+                b'.' | b'#' => {},
+                _ => return Err("one of `.`, `#`, `S` or `E`"),
+            }
+            Ok(bool::from_byte(b))
+        })?;
+        Ok(Maze {
+            walls,
+            start_pos: start_pos.ok_or_else(|| error::Error::new(16, 0, 0, "a start tile (`S`)", "none"))?,
+            end_pos: end_pos.ok_or_else(|| error::Error::new(16, 0, 0, "an end tile (`E`)", "none"))?,
+        })
+    }
+
+    // Moving forward costs 1 and turning in place costs 1000.
+    fn moves(&self, (pos, dir): &State) -> Vec<(State, i32)> {
+        let mut res = Vec::with_capacity(3);
+        if let Some(forward) = self.walls.step(*pos, *dir).filter(|f| !self.walls[*f]) {
+            res.push(((forward, *dir), 1));
+        }
+        res.push(((*pos, dir.turn_left()), 1000));
+        res.push(((*pos, dir.turn_right()), 1000));
+        res
+    }
+
+    // Search the maze, starting from the start position facing East and
+    // terminating once all of the best paths to the end position are found.
+    fn search(&self) -> Search<State, i32> {
+        dijkstra(
+            [(self.start_pos, Direction::East)],
+            |s| self.moves(s),
+            |(pos, _)| *pos == self.end_pos)
+    }
+
+    fn frame(&self, searched: &Grid<bool>) -> viz::Frame {
+        Grid::from_fn(self.walls.width(), self.walls.height(), |pos| {
+            if pos == self.start_pos || pos == self.end_pos {
+                viz::ACTOR
+            } else if self.walls[pos] {
+                viz::WALL
+            } else if searched[pos] {
+                viz::VISITED
+            } else {
+                viz::EMPTY
+            }
+        })
+    }
+
+    // The set of tiles that are on at least one of the best paths.
+    fn best_tiles(&self, search: &Search<State, i32>) -> HashSet<Pos> {
+        search.dag_states(search.goals().iter().copied())
+            .into_iter()
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+}
+
+impl std::fmt::Display for Maze {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (y, row) in self.walls.rows().enumerate() {
+            for (x, wall) in row.iter().enumerate() {
+                if self.start_pos == (y, x) {
+                    write!(f, "S")?;
+                } else if self.end_pos == (y, x) {
+                    write!(f, "E")?;
+                } else {
+                    write!(f, "{}", wall.to_byte() as char)?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+pub type Input = Maze;
+
+pub fn parse(input: &str) -> error::Result<Input> {
+    let grid = Maze::from(input)?;
+    trace::emit(|| Event::Grid { source: "day16", label: "maze", step: 0, grid: grid.to_string() });
+    Ok(grid)
+}
+
+pub fn solve_part1(grid: &Input) -> error::Result<i32> {
+    grid.search().goal_cost()
+        .ok_or_else(|| error::Error::new(16, 0, 0, "a path from `S` to `E`", "none"))
+}
+
+pub fn solve_part2(grid: &Input) -> i32 {
+    let search = grid.search();
+    grid.best_tiles(&search).len() as i32
+}
+
+pub fn try_part1(input: &str) -> error::Result<i32> {
+    solve_part1(&parse(input)?)
+}
+
+pub fn try_part2(input: &str) -> error::Result<i32> {
+    Ok(solve_part2(&parse(input)?))
+}
+
+/// Records the search spreading through the maze, one frame per state
+/// expanded. The last frame highlights the tiles on the best paths.
+pub fn record(input: &str, rec: &mut viz::Recorder) -> error::Result<()> {
+    let grid = Maze::from(input)?;
+    let mut searched = Grid::new(grid.walls.width(), grid.walls.height(), false);
+    let search = dijkstra(
+        [(grid.start_pos, Direction::East)],
+        |s| {
+            searched[s.0] = true;
+            rec.step(|| grid.frame(&searched));
+            grid.moves(s)
+        },
+        |(pos, _)| *pos == grid.end_pos);
+    let mut frame = grid.frame(&searched);
+    for pos in grid.best_tiles(&search) {
+        frame[pos] = viz::HIGHLIGHT;
+    }
+    rec.finish(frame);
+    Ok(())
+}
+
+#[aoc(day16, part1)]
+pub fn part1(input: &str) -> i32 {
+    try_part1(input).unwrap()
+}
+
+#[aoc(day16, part2)]
+pub fn part2(input: &str) -> i32 {
+    try_part2(input).unwrap()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::expected;
+
+    static TEST_INPUT: &str = include_str!("../../examples/2024/day16/a.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(expected(16, 1), part1(include_str!("../../input/2024/day16.txt")).into());
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(expected(16, 2), part2(include_str!("../../input/2024/day16.txt")).into());
+    }
+
+    #[test]
+    fn test_record() {
+        let mut rec = viz::Recorder::new(100);
+        record(TEST_INPUT, &mut rec).unwrap();
+        let last = rec.frames().last().unwrap();
+        assert_eq!(45, last.iter().filter(|(_, &c)| c == viz::HIGHLIGHT).count());
+        assert!(rec.frames().len() <= 100);
+    }
+}
@@ -1,7 +1,7 @@
 
 // https://adventofcode.com/2024/day/17
 
-use scanf::sscanf;
+use crate::error::{self, lines, Line};
 
 #[derive(Default, Copy, Clone, Eq, PartialEq, Debug)]
 struct Regfile {
@@ -327,53 +327,56 @@ impl Instruction {
     }
 }
 
-struct Simulator {
+/// The computer's starting registers and its program.
+pub struct Simulator {
     regfile: Regfile,
     program: Vec<Instruction>,
     program_str: Vec<u8>,
 }
 
 impl Simulator {
-    pub fn from(input: &str) -> Self {
+    pub fn from(input: &str) -> error::Result<Self> {
         let mut regfile = Regfile::default();
-        let mut program = Vec::new();
-        let mut program_str: Vec<u8> = Vec::new();
-        let mut lines = input.lines();
-
-        let mut r = 0;
-        sscanf!(lines.next().unwrap(), "Register A: {}", r).expect("Failed to parse");
-        regfile.a = r;
-        sscanf!(lines.next().unwrap(), "Register B: {}", r).expect("Failed to parse");
-        regfile.b = r;
-        sscanf!(lines.next().unwrap(), "Register C: {}", r).expect("Failed to parse");
-        regfile.c = r;
-
-        lines.next();
-        let mut chars = lines.next().unwrap().chars();
-        for c in chars.by_ref() {
-            if c == ' ' {
-                break;
-            }
+        let mut lines = lines(17, input);
+        let mut next_line = |expected: &str| {
+            lines.next().ok_or_else(|| error::end_of_input(17, input, expected))
+        };
+        let register = |line: Line, name: &str| -> error::Result<u64> {
+            let r = line.expect(line.text, &format!("Register {}: ", name))?;
+            line.parse(r, "a register value")
+        };
+
+        regfile.a = register(next_line("register A")?, "A")?;
+        regfile.b = register(next_line("register B")?, "B")?;
+        regfile.c = register(next_line("register C")?, "C")?;
+
+        let line = next_line("a blank line")?;
+        if !line.text.is_empty() {
+            return Err(line.error(line.text, "a blank line"));
         }
-        let mut i = chars.next().unwrap();
-        program_str.push(i.to_digit(10).unwrap() as u8);
-        for o in chars {
-            if !o.is_ascii_digit() {
-                continue;
+        let line = next_line("a program")?;
+        let mut program_str: Vec<u8> = Vec::new();
+        let rest = line.expect(line.text, "Program: ")?;
+        for part in rest.split(',') {
+            match part.as_bytes() {
+                [b @ b'0'..=b'7'] => program_str.push(b - b'0'),
+                _ => return Err(line.error(part, "a 3-bit number")),
             }
-            program.push(Instruction::from(i, o));
-            program_str.push(o.to_digit(10).unwrap() as u8);
-            i = o;
         }
 
-        Simulator {
+        // Jumps can land on any position, so decode an instruction at each one.
+        let program = program_str.windows(2)
+            .map(|w| Instruction::from((b'0' + w[0]) as char, (b'0' + w[1]) as char))
+            .collect();
+
+        Ok(Simulator {
             regfile,
             program,
             program_str,
-        }
+        })
     }
 
-    fn exec(&mut self) -> String {
+    fn exec(&self) -> String {
         let mut out = String::new();
         let mut rf = self.regfile;
         while let Some(i) = self.program.get(rf.pc) {
@@ -472,54 +475,63 @@ impl Simulator {
         Some(0)
     }
 
-    fn find_a(&self) -> u128 {
+    fn find_a(&self) -> Option<u128> {
         let mut a = [Bit::Zero; 128];
         for i in 0..128 {
             a[i as usize] = Bit::A(i);
         }
-        self.try_find_a(a).unwrap()
+        self.try_find_a(a)
     }
 }
 
+pub type Input = Simulator;
+
+pub fn parse(input: &str) -> error::Result<Input> {
+    Simulator::from(input)
+}
+
+pub fn solve_part1(sim: &Input) -> String {
+    sim.exec()
+}
+
+pub fn solve_part2(sim: &Input) -> error::Result<u128> {
+    sim.find_a()
+        .ok_or_else(|| error::Error::new(17, 0, 0, "a program that can output itself", "none"))
+}
+
+pub fn try_part1(input: &str) -> error::Result<String> {
+    Ok(solve_part1(&parse(input)?))
+}
+
+pub fn try_part2(input: &str) -> error::Result<u128> {
+    solve_part2(&parse(input)?)
+}
+
 #[aoc(day17, part1)]
 pub fn part1(input: &str) -> String {
-    Simulator::from(input).exec()
+    try_part1(input).unwrap()
 }
 
 #[aoc(day17, part2)]
 pub fn part2(input: &str) -> u128 {
-    Simulator::from(input).find_a()
+    try_part2(input).unwrap()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    static TEST_INPUT_A: &str = "\
-        Register A: 729\n\
-        Register B: 0\n\
-        Register C: 0\n\
-        \n\
-        Program: 0,1,5,4,3,0";
-
-    static TEST_INPUT_B: &str = "\
-        Register A: 2024\n\
-        Register B: 0\n\
-        Register C: 0\n\
-        \n\
-        Program: 0,3,5,4,3,0";
+    use crate::manifest::expected;
 
     #[test]
     fn test_part1() {
-        assert_eq!("4,6,3,5,6,3,5,2,1,0", part1(TEST_INPUT_A));
+        assert_eq!("day 17, line 5, column 12: expected a 3-bit number, found `8`",
+            try_part1("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 0,8").unwrap_err().to_string());
 
-        assert_eq!("7,0,3,1,2,6,3,7,1", part1(include_str!("../input/2024/day17.txt")));
+        assert_eq!(expected(17, 1), part1(include_str!("../../input/2024/day17.txt")).into());
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(117440, part2(TEST_INPUT_B));
-
-        assert_eq!(109020013201563, part2(include_str!("../input/2024/day17.txt")));
+        assert_eq!(expected(17, 2), part2(include_str!("../../input/2024/day17.txt")).into());
     }
 }
@@ -0,0 +1,155 @@
+// https://adventofcode.com/2024/day/18
+
+use crate::error::{self, lines};
+use crate::grid::{Grid, Pos};
+use crate::registry::Params;
+use crate::search::dijkstra;
+
+/// Where each byte falls, in order, and the size of the memory space.
+pub struct Input {
+    pub dim: usize,
+    pub bytes: Vec<Pos>,
+}
+
+struct MemorySpace {
+    walls: Grid<bool>,
+    start_pos: Pos,
+    end_pos: Pos,
+}
+
+impl MemorySpace {
+    // The memory space after the first `count` bytes have fallen.
+    fn from(input: &Input, count: usize) -> Self {
+        let dim = input.dim;
+        let mut walls: Grid<bool> = Grid::new(dim, dim, false);
+        for &pos in input.bytes.iter().take(count) {
+            walls[pos] = true;
+        }
+        MemorySpace {
+            walls,
+            start_pos: (0, 0),
+            end_pos: (dim-1, dim-1),
+        }
+    }
+
+    // Search the grid, starting from the start position and terminating when
+    // the end position is found.
+    fn bfs(&self) -> Option<i32> {
+        dijkstra(
+            [self.start_pos],
+            |pos: &Pos| {
+                self.walls.neighbors4(*pos)
+                    .filter(|(_, n)| !self.walls[*n])
+                    .map(|(_, n)| (n, 1))
+                    .collect::<Vec<(Pos, i32)>>()
+            },
+            |pos| *pos == self.end_pos,
+        ).goal_cost()
+    }
+
+    // Binary search for the blocking wall.
+    fn find_blocker(input: &Input) -> error::Result<String> {
+        // Check that the exit is blocked eventually.
+        if MemorySpace::from(input, usize::MAX).bfs().is_some() {
+            return Err(error::Error::new(18, input.bytes.len() + 1, 0, "a byte that blocks the exit", "end of input"));
+        }
+        let mut blocker_min = input.dim-2;
+        let mut blocker_max = input.bytes.len()-1;
+        loop {
+            if blocker_min == blocker_max {
+                break;
+            }
+            let blocker = blocker_min + (blocker_max - blocker_min) / 2;
+            if MemorySpace::from(input, blocker).bfs().is_none() {
+                blocker_max = blocker;
+            } else {
+                blocker_min = blocker + 1
+            }
+        }
+        let (y, x) = input.bytes[blocker_min-1];
+        Ok(format!("{},{}", x, y))
+    }
+}
+
+// Positions are kept as (y, x), the order the grid indexes them in.
+fn parse_with(input: &str, dim: usize) -> error::Result<Input> {
+    let bytes = lines(18, input).map(|line| {
+        let (x, y) = line.split_once(line.text, ",")?;
+        let x: usize = line.parse(x, format!("an x coordinate below {}", dim))?;
+        let y: usize = line.parse(y, format!("a y coordinate below {}", dim))?;
+        if x >= dim || y >= dim {
+            return Err(line.error_line(format!("a position within {}x{}", dim, dim)));
+        }
+        Ok((y, x))
+    }).collect::<error::Result<_>>()?;
+    Ok(Input { dim, bytes })
+}
+
+pub fn parse(input: &str) -> error::Result<Input> {
+    parse_with(input, 71)
+}
+
+// The length of the path to the exit after `count` bytes have fallen.
+fn shortest_path(input: &Input, count: usize) -> error::Result<i32> {
+    MemorySpace::from(input, count).bfs()
+        .ok_or_else(|| error::Error::new(18, 0, 0, "a path to the exit", "none"))
+}
+
+pub fn solve_part1(input: &Input) -> error::Result<i32> {
+    shortest_path(input, 1024)
+}
+
+pub fn solve_part2(input: &Input) -> error::Result<String> {
+    MemorySpace::find_blocker(input)
+}
+
+pub fn try_part1(input: &str) -> error::Result<i32> {
+    solve_part1(&parse(input)?)
+}
+
+pub fn try_part2(input: &str) -> error::Result<String> {
+    solve_part2(&parse(input)?)
+}
+
+/// `dim` overrides the size of the memory space, and `count` how many bytes
+/// fall before part 1's search.
+pub const PARAMS: &[&str] = &["dim", "count"];
+
+pub fn try_part1_with(input: &str, params: &Params) -> error::Result<i32> {
+    let input = parse_with(input, params.get(18, "dim", 71)?)?;
+    shortest_path(&input, params.get(18, "count", 1024)?)
+}
+
+pub fn try_part2_with(input: &str, params: &Params) -> error::Result<String> {
+    solve_part2(&parse_with(input, params.get(18, "dim", 71)?)?)
+}
+
+#[aoc(day18, part1)]
+pub fn part1(input: &str) -> i32 {
+    try_part1(input).unwrap()
+}
+
+#[aoc(day18, part2)]
+pub fn part2(input: &str) -> String {
+    try_part2(input).unwrap()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::expected;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(expected(18, 1), part1(include_str!("../../input/2024/day18.txt")).into());
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!("day 18, line 2: expected a position within 7x7, found `7,0`",
+            parse_with("0,1\n7,0", 7).err().unwrap().to_string());
+
+        assert_eq!(expected(18, 2), part2(include_str!("../../input/2024/day18.txt")).into());
+    }
+}
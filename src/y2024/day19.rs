@@ -0,0 +1,236 @@
+
+// https://adventofcode.com/2024/day/19
+
+use std::{collections::HashSet, hash::Hash, io::BufRead};
+use num_traits::int::PrimInt;
+use rayon::prelude::*;
+use crate::error::{self, lines, Line};
+use crate::parallel;
+use crate::stream::{LineReader, CHUNK_LINES};
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+struct Towel<T> 
+    where T: PrimInt + Hash + Eq
+{
+    code: T,
+}
+
+impl<T: PrimInt + Hash + Eq> Towel<T> {
+    fn from_bytes(input: &[u8]) -> Self {
+        let mut code: T = T::zero();
+        // Hoping that the compiler understands this as an unaligned move and mask.
+        for (i, b) in input.iter().enumerate() {
+            code = code | T::from(*b).unwrap().unsigned_shl(8 * i as u32);
+        }
+        Towel { code }
+    }
+    fn from_bytes_set(input: &[u8], max_len: usize) -> Vec<Self> {
+        let len = std::cmp::min(input.len(), max_len);
+        let input = &input[0..len];
+        let mut res = Vec::with_capacity(len);
+        let mut code: T = T::zero();
+        for (i, b) in input.iter().enumerate() {
+            code = code | T::from(*b).unwrap().unsigned_shl(8 * i as u32);
+            res.push(Towel { code });
+        }
+        res
+    }
+}
+
+/// The towel patterns, grouped by length. Patterns too long to pack into a T
+/// are kept as they are, which is slower.
+pub struct TowelSet<T>
+    where T: PrimInt + Hash + Eq
+{
+    sets: Vec<HashSet<Towel<T>>>,
+    long: Vec<(usize, HashSet<Vec<u8>>)>,
+}
+
+impl<T: PrimInt + Hash + Eq> TowelSet<T> {
+    fn from_str(input: &str) -> Self {
+        let mut sets = Vec::new();
+        let mut long: Vec<(usize, HashSet<Vec<u8>>)> = Vec::new();
+        for item in input.split(", ") {
+            if item.len() > size_of::<T>() {
+                match long.iter_mut().find(|(len, _)| *len == item.len()) {
+                    Some((_, set)) => {
+                        set.insert(item.as_bytes().to_vec());
+                    },
+                    None => long.push((item.len(), HashSet::from([item.as_bytes().to_vec()]))),
+                }
+                continue;
+            }
+            while item.len() > sets.len() {
+                sets.push(HashSet::new());
+            }
+            let set = &mut sets[item.len() - 1];
+            set.insert(Towel::from_bytes(item.as_bytes()));
+        }
+        TowelSet { sets, long }
+    }
+
+    fn contains(&self, item: Towel<T>, len: usize) -> bool {
+        self.sets[len-1].contains(&item)
+    }
+
+    fn count_builds(&self, line: &str) -> u64 {
+        let line = line.as_bytes();
+        let mut valid_starts = vec![0; line.len()+1];
+        valid_starts[0] = 1;
+        for i in 0..line.len() {
+            if valid_starts[i] == 0 {
+                continue;
+            }
+            let towels: Vec<Towel<T>> = Towel::from_bytes_set(&line[i..], self.sets.len());
+            for (j, t) in towels.iter().enumerate() {
+                if self.contains(*t, j+1) {
+                    valid_starts[i+j+1] += valid_starts[i];
+                }
+            }
+            for (len, set) in &self.long {
+                if line.len() - i >= *len && set.contains(&line[i..i+len]) {
+                    valid_starts[i+len] += valid_starts[i];
+                }
+            }
+        }
+        valid_starts[line.len()]
+    }
+}
+
+const COLORS: &str = "one of `w`, `u`, `b`, `r` or `g`";
+
+fn parse_towels(line: &Line) -> error::Result<TowelSet<u64>> {
+    for item in line.text.split(", ") {
+        if item.is_empty() {
+            return Err(line.error(item, "a towel pattern"));
+        }
+        if let Some(i) = item.bytes().position(|b| !b"wubrg".contains(&b)) {
+            return Err(line.error(&item[i..], COLORS));
+        }
+    }
+    Ok(TowelSet::from_str(line.text))
+}
+
+fn check_separator(line: &Line) -> error::Result<()> {
+    if !line.text.is_empty() {
+        return Err(line.error(line.text, "a blank line"));
+    }
+    Ok(())
+}
+
+fn check_design(line: &Line) -> error::Result<()> {
+    if let Some(i) = line.text.bytes().position(|b| !b"wubrg".contains(&b)) {
+        return Err(line.error(&line.text[i..], COLORS));
+    }
+    Ok(())
+}
+
+/// The towel patterns and the list of designs.
+pub type Input<'a> = (TowelSet<u64>, Vec<&'a str>);
+
+pub fn parse(input: &str) -> error::Result<Input<'_>> {
+    let mut lines = lines(19, input);
+    let line = lines.next().ok_or_else(|| error::end_of_input(19, input, "a list of towel patterns"))?;
+    let ts = parse_towels(&line)?;
+
+    if let Some(line) = lines.next() {
+        check_separator(&line)?;
+    }
+    let mut designs = Vec::new();
+    for line in lines {
+        check_design(&line)?;
+        designs.push(line.text);
+    }
+    Ok((ts, designs))
+}
+
+// Reads the designs a chunk at a time and counts each chunk in parallel, so
+// only the towel patterns and one chunk are in memory.
+fn stream_sum(input: impl BufRead, count: impl Fn(&TowelSet<u64>, &str) -> u64 + Sync) -> error::Result<u64> {
+    let mut reader = LineReader::new(19, input);
+    let ts = match reader.next_line()? {
+        Some(line) => parse_towels(&line)?,
+        None => return Err(reader.end_of_input("a list of towel patterns")),
+    };
+    if let Some(line) = reader.next_line()? {
+        check_separator(&line)?;
+    }
+
+    let mut total = 0;
+    reader.for_each_chunk(CHUNK_LINES, |lines| {
+        lines.iter().try_for_each(check_design)?;
+        total += parallel::install(|| lines.par_iter().map(|line| count(&ts, line.text)).sum::<u64>());
+        Ok(())
+    })?;
+    Ok(total)
+}
+
+pub fn stream_part1(input: impl BufRead) -> error::Result<u64> {
+    stream_sum(input, |ts, line| if ts.count_builds(line) > 0 { 1 } else { 0 })
+}
+
+pub fn stream_part2(input: impl BufRead) -> error::Result<u64> {
+    stream_sum(input, |ts, line| ts.count_builds(line))
+}
+
+pub fn solve_part1((ts, designs): &Input) -> u64 {
+    parallel::install(|| designs.par_iter().map(|line| {
+        if ts.count_builds(line) > 0 { 1 } else { 0 }
+    }).sum())
+}
+
+pub fn solve_part2((ts, designs): &Input) -> u64 {
+    parallel::install(|| designs.par_iter().map(|line| {
+        ts.count_builds(line)
+    }).sum())
+}
+
+pub fn try_part1(input: &str) -> error::Result<u64> {
+    Ok(solve_part1(&parse(input)?))
+}
+
+pub fn try_part2(input: &str) -> error::Result<u64> {
+    Ok(solve_part2(&parse(input)?))
+}
+
+#[aoc(day19, part1)]
+pub fn part1(input: &str) -> u64 {
+    try_part1(input).unwrap()
+}
+
+#[aoc(day19, part2)]
+pub fn part2(input: &str) -> u64 {
+    try_part2(input).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::expected;
+
+    static TEST_INPUT: &str = include_str!("../../examples/2024/day19/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Ok(6), stream_part1(TEST_INPUT.as_bytes()));
+        assert_eq!("day 19, line 4, column 2: expected one of `w`, `u`, `b`, `r` or `g`, found `x`",
+            stream_part1("r, w\n\nrw\nrx".as_bytes()).unwrap_err().to_string());
+        assert_eq!("day 19, line 2, column 1: expected a blank line, found `rw`",
+            try_part1("r, w\nrw\nwr").unwrap_err().to_string());
+        assert_eq!("day 19, line 2, column 1: expected a blank line, found `, b`",
+            stream_part1("r, w\n, b\n\nrw".as_bytes()).unwrap_err().to_string());
+        assert_eq!(Ok(0), try_part1("r, w"));
+
+        // Patterns too long to pack into a u64.
+        assert_eq!(Ok(2), try_part2("r, wubrgwubr, wubrgwub\n\nwubrgwubrr\nwubrgwubrgwubrgwubr"));
+        assert_eq!(Ok(1), try_part1("wubrgwubrgw, g\n\nwubrgwubrgwg\nwubrgwubrgg"));
+
+        assert_eq!(expected(19, 1), part1(include_str!("../../input/2024/day19.txt")).into());
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(expected(19, 2), part2(include_str!("../../input/2024/day19.txt")).into());
+        assert_eq!(expected(19, 2), stream_part2(include_bytes!("../../input/2024/day19.txt").as_slice()).unwrap().into());
+    }
+}
@@ -0,0 +1,199 @@
+
+// https://adventofcode.com/2024/day/2
+
+use std::io::BufRead;
+use std::ops::RangeInclusive;
+use crate::error::{self, lines, Line};
+use crate::stream::LineReader;
+
+fn parse_report(line: &Line) -> error::Result<Vec<i64>> {
+    line.text.split_whitespace().map(|word| line.parse(word, "a level")).collect()
+}
+
+/// The levels of each report.
+pub type Input = Vec<Vec<i64>>;
+
+pub fn parse(input: &str) -> error::Result<Input> {
+    lines(2, input).map(|line| parse_report(&line)).collect()
+}
+
+/// What makes a report safe: after removing at most `max_removals` levels,
+/// every step between the levels that are left is within `steps` going up, or
+/// within `steps` going down.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Tolerance {
+    pub steps: RangeInclusive<i64>,
+    pub max_removals: usize,
+}
+
+impl Tolerance {
+    /// The rules for part 1.
+    pub const STRICT: Tolerance = Tolerance { steps: 1..=3, max_removals: 0 };
+    /// The rules for part 2, with the Problem Dampener.
+    pub const DAMPENED: Tolerance = Tolerance { steps: 1..=3, max_removals: 1 };
+
+    /// The indices of the levels to remove to make `report` safe, which is
+    /// empty if it is safe already, or None if it can't be made safe. When
+    /// there is a choice, this is one of the smallest sets of levels.
+    pub fn check(&self, report: &[i64]) -> Option<Vec<usize>> {
+        let up = self.fewest_removals(report, false);
+        let down = self.fewest_removals(report, true);
+        match (up, down) {
+            (Some(up), Some(down)) if down.len() < up.len() => Some(down),
+            (Some(up), _) => Some(up),
+            (None, down) => down,
+        }
+    }
+
+    // The fewest levels to remove so that each step between the levels left
+    // is within `steps`, going down if `descending`. A level that is kept can
+    // only follow one of the max_removals + 1 levels before it, since skipping
+    // more would remove too many, so this is O(n * max_removals).
+    fn fewest_removals(&self, report: &[i64], descending: bool) -> Option<Vec<usize>> {
+        let n = report.len();
+        let fits = |from: i64, to: i64| {
+            let step = if descending { from.checked_sub(to) } else { to.checked_sub(from) };
+            step.is_some_and(|step| self.steps.contains(&step))
+        };
+        // removals[i] is the fewest levels removed before level i when it is
+        // kept, and prev[i] is the level kept before it.
+        let mut removals: Vec<usize> = vec![usize::MAX; n];
+        let mut prev: Vec<Option<usize>> = vec![None; n];
+        for i in 0..n {
+            if i <= self.max_removals {
+                removals[i] = i;
+            }
+            for j in i.saturating_sub(self.max_removals.saturating_add(1))..i {
+                if removals[j] == usize::MAX || !fits(report[j], report[i]) {
+                    continue;
+                }
+                let count = removals[j] + (i - j - 1);
+                // On a tie, keep the nearer level, which removes earlier ones.
+                if count <= removals[i] {
+                    removals[i] = count;
+                    prev[i] = Some(j);
+                }
+            }
+        }
+
+        // The last level kept is the one that leaves the fewest removals,
+        // counting the levels after it.
+        let (last, count) = (0..n).map(|i| (i, removals[i].saturating_add(n - 1 - i))).min_by_key(|&(_, count)| count)
+            .unwrap_or((0, 0));
+        if count > self.max_removals {
+            return None;
+        }
+        let mut kept = vec![false; n];
+        let mut level = (n > 0).then_some(last);
+        while let Some(i) = level {
+            kept[i] = true;
+            level = prev[i];
+        }
+        Some((0..n).filter(|&i| !kept[i]).collect())
+    }
+}
+
+/// For each report, the levels that were removed to make it safe, or None if
+/// it can't be.
+pub fn diagnose(reports: &Input, tolerance: &Tolerance) -> Vec<Option<Vec<usize>>> {
+    reports.iter().map(|report| tolerance.check(report)).collect()
+}
+
+fn count_safe(reports: &Input, tolerance: &Tolerance) -> u64 {
+    reports.iter().filter(|report| tolerance.check(report).is_some()).count() as u64
+}
+
+pub fn solve_part1(reports: &Input) -> u64 {
+    count_safe(reports, &Tolerance::STRICT)
+}
+
+pub fn solve_part2(reports: &Input) -> u64 {
+    count_safe(reports, &Tolerance::DAMPENED)
+}
+
+pub fn try_part1(input: &str) -> error::Result<u64> {
+    Ok(solve_part1(&parse(input)?))
+}
+
+pub fn try_part2(input: &str) -> error::Result<u64> {
+    Ok(solve_part2(&parse(input)?))
+}
+
+// Checks one report at a time, so only the current line is in memory.
+fn stream_count(input: impl BufRead, tolerance: &Tolerance) -> error::Result<u64> {
+    let mut total: u64 = 0;
+    LineReader::new(2, input).for_each(|line| {
+        if tolerance.check(&parse_report(&line)?).is_some() {
+            total += 1;
+        }
+        Ok(())
+    })?;
+    Ok(total)
+}
+
+pub fn stream_part1(input: impl BufRead) -> error::Result<u64> {
+    stream_count(input, &Tolerance::STRICT)
+}
+
+pub fn stream_part2(input: impl BufRead) -> error::Result<u64> {
+    stream_count(input, &Tolerance::DAMPENED)
+}
+
+#[aoc(day2, part1)]
+pub fn part1(input: &str) -> u64 {
+    try_part1(input).unwrap()
+}
+
+#[aoc(day2, part2)]
+pub fn part2(input: &str) -> u64 {
+    try_part2(input).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::expected;
+
+    static TEST_INPUT: &str = include_str!("../../examples/2024/day2/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Ok(2), stream_part1(TEST_INPUT.as_bytes()));
+
+        assert_eq!(expected(2, 1), part1(include_str!("../../input/2024/day2.txt")).into());
+        assert_eq!(expected(2, 1), stream_part1(include_bytes!("../../input/2024/day2.txt").as_slice()).unwrap().into());
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Ok(4), stream_part2(TEST_INPUT.as_bytes()));
+        assert_eq!("day 2, line 2, column 3: expected a level, found `x`",
+            stream_part2("1 2\n3 x".as_bytes()).unwrap_err().to_string());
+
+        assert_eq!(expected(2, 2), part2(include_str!("../../input/2024/day2.txt")).into());
+        assert_eq!(expected(2, 2), stream_part2(include_bytes!("../../input/2024/day2.txt").as_slice()).unwrap().into());
+    }
+
+    #[test]
+    fn test_check() {
+        let reports = parse(TEST_INPUT).unwrap();
+        let removed = vec![Some(vec![]), None, None, Some(vec![1]), Some(vec![2]), Some(vec![])];
+        assert_eq!(removed, diagnose(&reports, &Tolerance::DAMPENED));
+        assert_eq!(vec![true, false, false, false, false, true],
+            diagnose(&reports, &Tolerance::STRICT).iter().map(Option::is_some).collect::<Vec<_>>());
+
+        // More removals, and other steps.
+        let tolerance = Tolerance { steps: 1..=3, max_removals: 2 };
+        assert_eq!(Some(vec![0, 3]), tolerance.check(&[9, 1, 2, 9, 3, 4]));
+        assert_eq!(Some(vec![1, 2]), tolerance.check(&[1, 9, 8, 2, 3]));
+        assert_eq!(None, tolerance.check(&[1, 9, 8, 7, 2, 3, 20]));
+        let unlimited = Tolerance { steps: 1..=3, max_removals: usize::MAX };
+        assert_eq!(Some(vec![1, 2, 3, 6]), unlimited.check(&[1, 9, 8, 7, 2, 3, 20]));
+        assert_eq!(Some(vec![]), tolerance.check(&[]));
+        assert_eq!(Some(vec![2]), tolerance.check(&[5, 6, 50]));
+        assert_eq!(Some(vec![0, 1]), tolerance.check(&[50, 40, 6, 5]));
+        let tolerance = Tolerance { steps: 0..=10, max_removals: 0 };
+        assert_eq!(Some(vec![]), tolerance.check(&[30, 30, 20, 11]));
+        assert_eq!(None, tolerance.check(&[i64::MIN, i64::MAX]));
+    }
+}
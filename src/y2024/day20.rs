@@ -0,0 +1,134 @@
+
+// https://adventofcode.com/2024/day/20
+
+use rayon::prelude::*;
+use crate::error;
+use crate::grid::{Grid, GridCell, Pos};
+use crate::parallel;
+use crate::registry::Params;
+use crate::search::dijkstra;
+
+/// The track's walls, and each tile on the path with its distance from the
+/// start.
+pub struct Racetrack {
+    walls: Grid<bool>,
+    start_pos: Pos,
+    path: Vec<(usize, usize, i32)>,
+}
+
+impl Racetrack {
+    fn from(input: &str) -> error::Result<Self> {
+        let mut start_pos = None;
+        let walls = Grid::try_parse_with(20, input, |pos, b| {
+            match b {
+                b'S' => start_pos = Some(pos),
+                b'.' | b'#' | b'E' => {},
+                _ => return Err("one of `.`, `#`, `S` or `E`"),
+            }
+            Ok(bool::from_byte(b))
+        })?;
+        Ok(Racetrack {
+            walls,
+            start_pos: start_pos.ok_or_else(|| error::Error::new(20, 0, 0, "a start tile (`S`)", "none"))?,
+            path: Vec::new(),
+        })
+    }
+
+    // Search the whole track from the start position and record the cost to
+    // reach each point on it, in order.
+    fn bfs(&mut self) {
+        let search = dijkstra(
+            [self.start_pos],
+            |pos: &Pos| {
+                self.walls.neighbors4(*pos)
+                    .filter(|(_, n)| !self.walls[*n])
+                    .map(|(_, n)| (n, 1))
+                    .collect::<Vec<(Pos, i32)>>()
+            },
+            |_| false);
+        self.path = search.distances().iter().map(|(pos, cost)| (pos.0, pos.1, *cost)).collect();
+        self.path.sort_unstable_by_key(|(_, _, cost)| *cost);
+    }
+
+    // Check each pair of points in the path and count the pairs that are within
+    // the maximum distance and provide at least the required savings for
+    // cheating.
+    // TODO: Try searching by proximity. For path-length N, this algo is O(N^2).
+    // Searching by proximity would be O(N*max_distance^2), which might be
+    // better especially for part 1 where max_distance is small.
+    fn find_cheats(&self, min_savings: i32, max_distance: usize) -> u32 {
+        parallel::install(|| (1..self.path.len()).into_par_iter().map(|i| {
+            let (ex, ey, ec) = self.path[i];
+            (0..i).map(|j| {
+                let (sx, sy, sc) = self.path[j];
+                let dist = ey.abs_diff(sy) + ex.abs_diff(sx);
+                if dist <= max_distance && sc + (dist as i32) + min_savings <= ec {
+                    1
+                } else {
+                    0
+                }
+            }).sum::<u32>()
+        }).sum())
+    }
+}
+
+pub type Input = Racetrack;
+
+pub fn parse(input: &str) -> error::Result<Input> {
+    let mut grid = Racetrack::from(input)?;
+    grid.bfs();
+    Ok(grid)
+}
+
+pub fn solve_part1(grid: &Input) -> u32 {
+    grid.find_cheats(100, 2)
+}
+
+pub fn solve_part2(grid: &Input) -> u32 {
+    grid.find_cheats(100, 20)
+}
+
+pub fn try_part1(input: &str) -> error::Result<u32> {
+    Ok(solve_part1(&parse(input)?))
+}
+
+pub fn try_part2(input: &str) -> error::Result<u32> {
+    Ok(solve_part2(&parse(input)?))
+}
+
+/// `min_savings` overrides how much time a cheat has to save to be counted.
+pub const PARAMS: &[&str] = &["min_savings"];
+
+pub fn try_part1_with(input: &str, params: &Params) -> error::Result<u32> {
+    Ok(parse(input)?.find_cheats(params.get(20, "min_savings", 100)?, 2))
+}
+
+pub fn try_part2_with(input: &str, params: &Params) -> error::Result<u32> {
+    Ok(parse(input)?.find_cheats(params.get(20, "min_savings", 100)?, 20))
+}
+
+#[aoc(day20, part1)]
+pub fn part1(input: &str) -> u32 {
+    try_part1(input).unwrap()
+}
+
+#[aoc(day20, part2)]
+pub fn part2(input: &str) -> u32 {
+    try_part2(input).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::expected;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(expected(20, 1), part1(include_str!("../../input/2024/day20.txt")).into());
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(expected(20, 2), part2(include_str!("../../input/2024/day20.txt")).into());
+    }
+}
@@ -1,6 +1,8 @@
 
 // https://adventofcode.com/2024/day/21
 
+use crate::error::{self, lines};
+
 const DPAD_UP: usize = 0;
 const DPAD_DOWN: usize = 1;
 const DPAD_LEFT: usize = 2;
@@ -168,7 +170,7 @@ const NUMPAD_MOVE_COST2: [[u64; 11]; 11] = {
     rc
 };
 
-const PART1_LUT: [u64; 4096] = {
+static PART1_LUT: [u64; 4096] = {
     let mut lut: [u64; 4096] = [0; 4096];
     
     let mut i = 0;
@@ -193,7 +195,7 @@ const PART1_LUT: [u64; 4096] = {
     lut
 };
 
-const PART2_LUT: [u64; 4096] = {
+static PART2_LUT: [u64; 4096] = {
     let mut lut: [u64; 4096] = [0; 4096];
     
     let mut i = 0;
@@ -219,38 +221,140 @@ const PART2_LUT: [u64; 4096] = {
 };
 
 
+// The lookups index by the first four bytes of a code, folded into 12 bits.
+fn lut_index(line: &str) -> usize {
+    let line = line.as_bytes();
+    let line: [u8; 4] = line[0..4].try_into().unwrap();
+    let line = u32::from_ne_bytes(line);
+    ((line ^ (line >> 12)) & 0xFFF) as usize
+}
+
+/// The lookup table index of each code.
+pub type Input = Vec<usize>;
+
+// Checks that every line is a code like "029A" before indexing by it.
+pub fn parse(input: &str) -> error::Result<Input> {
+    lines(21, input).map(|line| {
+        let bytes = line.text.as_bytes();
+        if let Some(i) = (0..3).find(|i| !bytes.get(*i).is_some_and(u8::is_ascii_digit)) {
+            return Err(line.error(&line.text[i.min(bytes.len())..], "a digit"));
+        }
+        if line.text.len() != 4 || bytes[3] != b'A' {
+            return Err(line.error(&line.text[3..], "`A` and the end of the line"));
+        }
+        Ok(lut_index(line.text))
+    }).collect()
+}
+
+pub fn solve_part1(codes: &Input) -> u64 {
+    codes.iter().map(|&i| PART1_LUT[i]).sum()
+}
+
+pub fn solve_part2(codes: &Input) -> u64 {
+    codes.iter().map(|&i| PART2_LUT[i]).sum()
+}
+
+pub fn try_part1(input: &str) -> error::Result<u64> {
+    Ok(solve_part1(&parse(input)?))
+}
+
+pub fn try_part2(input: &str) -> error::Result<u64> {
+    Ok(solve_part2(&parse(input)?))
+}
+
+// These skip the checks, and index straight from the text.
 #[aoc(day21, part1)]
 pub fn part1(input: &str) -> u64 {
-    input.lines().map(|line| {
-        let line = line.as_bytes();
-        let line: [u8; 4] = line[0..4].try_into().unwrap();
-        let line = u32::from_ne_bytes(line);
-        let line = (line ^ (line >> 12)) & 0xFFF;
-        PART1_LUT[line as usize]
-    }).sum()
+    input.lines().map(|line| PART1_LUT[lut_index(line)]).sum()
 }
 
 #[aoc(day21, part2)]
 pub fn part2(input: &str) -> u64 {
-    input.lines().map(|line| {
-        let line = line.as_bytes();
-        let line: [u8; 4] = line[0..4].try_into().unwrap();
-        let line = u32::from_ne_bytes(line);
-        let line = (line ^ (line >> 12)) & 0xFFF;
-        PART2_LUT[line as usize]
-    }).sum()
+    input.lines().map(|line| PART2_LUT[lut_index(line)]).sum()
+}
+
+// The keypads, with a space for the gap that the robots must avoid.
+const REFERENCE_NUMPAD: [&[u8]; 4] = [b"789", b"456", b"123", b" 0A"];
+const REFERENCE_DPAD: [&[u8]; 2] = [b" ^A", b"<v>"];
+
+fn reference_find(pad: &[&[u8]], key: u8) -> (usize, usize) {
+    pad.iter().enumerate()
+        .find_map(|(row, keys)| Some((row, keys.iter().position(|k| *k == key)?)))
+        .unwrap()
+}
+
+// Every order of the moves from `from` to `to` that doesn't cross the gap,
+// each followed by `A`.
+fn reference_routes(pad: &[&[u8]], from: u8, to: u8) -> Vec<Vec<u8>> {
+    let (start, end) = (reference_find(pad, from), reference_find(pad, to));
+    let mut routes = Vec::new();
+    let mut stack = vec![(start, Vec::new())];
+    while let Some((pos, route)) = stack.pop() {
+        if pos == end {
+            routes.push([route, vec![b'A']].concat());
+            continue;
+        }
+        let mut moves = Vec::new();
+        if pos.0 < end.0 { moves.push((b'v', (pos.0 + 1, pos.1))); }
+        if pos.0 > end.0 { moves.push((b'^', (pos.0 - 1, pos.1))); }
+        if pos.1 < end.1 { moves.push((b'>', (pos.0, pos.1 + 1))); }
+        if pos.1 > end.1 { moves.push((b'<', (pos.0, pos.1 - 1))); }
+        for (arrow, next) in moves {
+            if pad[next.0][next.1] != b' ' {
+                stack.push((next, [route.clone(), vec![arrow]].concat()));
+            }
+        }
+    }
+    routes
+}
+
+// The fewest presses needed on the outermost keypad to type `keys` on `pad`,
+// with `dpads` directional keypads in between.
+fn reference_presses(pad: &[&[u8]], keys: &[u8], dpads: usize,
+        memo: &mut std::collections::HashMap<(Vec<u8>, usize), u64>) -> u64 {
+    if dpads == 0 {
+        return keys.len() as u64;
+    }
+    let key = (keys.to_vec(), dpads);
+    if let Some(presses) = memo.get(&key) {
+        return *presses;
+    }
+    let mut total = 0;
+    let mut from = b'A';
+    for &to in keys {
+        total += reference_routes(pad, from, to).iter()
+            .map(|route| reference_presses(&REFERENCE_DPAD, route, dpads - 1, memo))
+            .min()
+            .unwrap();
+        from = to;
+    }
+    memo.insert(key, total);
+    total
+}
+
+// A straightforward version of the lookup tables, which searches every route
+// on every keypad. There are `robots` robots at directional keypads. This is
+// what the tables are checked against in crate::diff.
+pub(crate) fn reference_complexity(input: &str, robots: usize) -> Option<u64> {
+    parse(input).ok()?;
+    let mut memo = std::collections::HashMap::new();
+    Some(input.lines().map(|code| {
+        let presses = reference_presses(&REFERENCE_NUMPAD, code.as_bytes(), robots + 1, &mut memo);
+        presses * code[..3].parse::<u64>().unwrap()
+    }).sum())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::manifest::expected;
 
-    static TEST_INPUT: &str = "029A\n980A\n179A\n456A\n379A";
+    static TEST_INPUT: &str = include_str!("../../examples/2024/day21/example.txt");
 
     #[test]
     fn test_part1() {
         // 999 distinct entries should be populated.
-        assert_eq!(999, PART1_LUT.iter().map(|v| {if *v > 0 {1} else {0}}).sum());
+        assert_eq!(999, PART1_LUT.iter().map(|v| {if *v > 0 {1} else {0}}).sum::<u32>());
 
         // Subsets of the example
         assert_eq!(68 *  29, part1("029A"));
@@ -258,13 +362,15 @@ mod tests {
         assert_eq!(68 * 179, part1("179A"));
         assert_eq!(64 * 456, part1("456A"));
         assert_eq!(64 * 379, part1("379A"));
-        assert_eq!(126384, part1(TEST_INPUT));
+        assert_eq!(Some(126384), reference_complexity(TEST_INPUT, 2));
+        assert_eq!("day 21, line 2, column 4: expected `A` and the end of the line, found `AA`",
+            try_part1("029A\n980AA").unwrap_err().to_string());
 
-        assert_eq!(137870, part1(include_str!("../input/2024/day21.txt")));
+        assert_eq!(expected(21, 1), part1(include_str!("../../input/2024/day21.txt")).into());
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(170279148659464, part2(include_str!("../input/2024/day21.txt")));
+        assert_eq!(expected(21, 2), part2(include_str!("../../input/2024/day21.txt")).into());
     }
 }
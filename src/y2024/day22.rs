@@ -0,0 +1,189 @@
+
+// https://adventofcode.com/2024/day/22
+
+use rayon::prelude::*;
+use std::collections::{HashSet,HashMap};
+use std::io::BufRead;
+use crate::error::{self, lines};
+use crate::parallel;
+use crate::stream::{LineReader, CHUNK_LINES};
+use crate::trace::{self, Event};
+
+fn advance(i: u32) -> u32 {
+    let o = (i <<  6) ^ i;
+    let o = o & 0xFFFFFF;
+    let o = (o >>  5) ^ o;
+    let o = (o << 11) ^ o;
+    o & 0xFFFFFF
+}
+
+fn advance_with_bytes(i: &mut u32, i_mod_10: &mut u32, pattern_bytes: &mut u32) {
+    *i = advance(*i);
+    let next_mod_10 = *i % 10;
+    let next_byte = 10 + next_mod_10 - *i_mod_10;
+    *pattern_bytes = *pattern_bytes << 8 | next_byte;
+    *i_mod_10 = next_mod_10;
+}
+
+/// Each buyer's initial secret number.
+pub type Input = Vec<u32>;
+
+pub fn parse(input: &str) -> error::Result<Input> {
+    lines(22, input).map(|line| line.parse(line.text, "a secret number")).collect()
+}
+
+pub fn solve_part1(secrets: &Input) -> u64 {
+    parallel::install(|| secrets.par_iter().map(|&val| {
+        let mut val = val;
+        for _ in 0..2000 {
+            val = advance(val);
+        }
+        val as u64
+    }).sum())
+}
+
+pub fn solve_part2(secrets: &Input) -> u32 {
+    let mut patterns: HashMap<u32, u32> = HashMap::new();
+    // How often a buyer's pattern is new to them, and how often it repeats.
+    let (mut firsts, mut repeats): (u64, u64) = (0, 0);
+    for &val in secrets {
+        let mut val = val;
+        let mut val_mod_10 = val % 10;
+        let mut pattern_bytes: u32 = 0;
+        let mut my_patterns: HashSet<u32> = HashSet::new();
+        for _ in 0..3 {
+            advance_with_bytes(&mut val, &mut val_mod_10, &mut pattern_bytes);
+        }
+        for _ in 3..2000 {
+            advance_with_bytes(&mut val, &mut val_mod_10, &mut pattern_bytes);
+            if my_patterns.insert(pattern_bytes) {
+                let p = patterns.entry(pattern_bytes).or_insert(0);
+                *p += val_mod_10;
+                firsts += 1;
+            } else {
+                repeats += 1;
+            }
+        }
+    }
+    trace::emit(|| Event::Cache { source: "day22", label: "buyer patterns", hits: repeats, misses: firsts });
+    let mut max = 0;
+    for (_, v) in patterns {
+        if v > max {
+            max = v;
+        }
+    }
+    max
+}
+
+pub fn try_part1(input: &str) -> error::Result<u64> {
+    Ok(solve_part1(&parse(input)?))
+}
+
+pub fn try_part2(input: &str) -> error::Result<u32> {
+    Ok(solve_part2(&parse(input)?))
+}
+
+// Reads the secrets a chunk at a time and advances each chunk in parallel.
+pub fn stream_part1(input: impl BufRead) -> error::Result<u64> {
+    let mut total = 0;
+    LineReader::new(22, input).for_each_chunk(CHUNK_LINES, |lines| {
+        let secrets: Vec<u32> = lines.iter()
+            .map(|line| line.parse(line.text, "a secret number"))
+            .collect::<error::Result<_>>()?;
+        total += parallel::install(|| secrets.into_par_iter().map(|mut val| {
+            for _ in 0..2000 {
+                val = advance(val);
+            }
+            val as u64
+        }).sum::<u64>());
+        Ok(())
+    })?;
+    Ok(total)
+}
+
+// Like solve_part2(), but reads one buyer at a time. The totals are u64s here,
+// since the inputs this is meant for have enough buyers to overflow a u32.
+pub fn stream_part2(input: impl BufRead) -> error::Result<u64> {
+    let mut patterns: HashMap<u32, u64> = HashMap::new();
+    let mut my_patterns: HashSet<u32> = HashSet::new();
+    LineReader::new(22, input).for_each(|line| {
+        let mut val: u32 = line.parse(line.text, "a secret number")?;
+        let mut val_mod_10 = val % 10;
+        let mut pattern_bytes: u32 = 0;
+        my_patterns.clear();
+        for _ in 0..3 {
+            advance_with_bytes(&mut val, &mut val_mod_10, &mut pattern_bytes);
+        }
+        for _ in 3..2000 {
+            advance_with_bytes(&mut val, &mut val_mod_10, &mut pattern_bytes);
+            if my_patterns.insert(pattern_bytes) {
+                *patterns.entry(pattern_bytes).or_insert(0) += val_mod_10 as u64;
+            }
+        }
+        Ok(())
+    })?;
+    Ok(patterns.into_values().max().unwrap_or(0))
+}
+
+// A straightforward version of solve_part2(), which keeps the price changes
+// as arrays instead of packing them into a u32. This is what solve_part2() is
+// checked against in crate::diff.
+pub(crate) fn reference_part2(input: &str) -> Option<u32> {
+    let mut totals: HashMap<[i32; 4], u32> = HashMap::new();
+    for mut secret in parse(input).ok()? {
+        let mut prices = vec![secret % 10];
+        for _ in 0..2000 {
+            secret = advance(secret);
+            prices.push(secret % 10);
+        }
+        let changes: Vec<i32> = prices.windows(2).map(|w| w[1] as i32 - w[0] as i32).collect();
+        let mut seen = HashSet::new();
+        for i in 4..prices.len() {
+            let pattern = [changes[i - 4], changes[i - 3], changes[i - 2], changes[i - 1]];
+            if seen.insert(pattern) {
+                *totals.entry(pattern).or_default() += prices[i];
+            }
+        }
+    }
+    Some(totals.values().copied().max().unwrap_or(0))
+}
+
+#[aoc(day22, part1)]
+pub fn part1(input: &str) -> u64 {
+    try_part1(input).unwrap()
+}
+
+#[aoc(day22, part2)]
+pub fn part2(input: &str) -> u32 {
+    try_part2(input).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::expected;
+
+    static TEST_INPUT_A: &str = include_str!("../../examples/2024/day22/a.txt");
+    static TEST_INPUT_B: &str = include_str!("../../examples/2024/day22/b.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!( 8685429, part1("1"));
+        assert_eq!( 4700978, part1("10"));
+        assert_eq!(15273692, part1("100"));
+        assert_eq!( 8667524, part1("2024"));
+        assert_eq!(Ok(37327623), stream_part1(TEST_INPUT_A.as_bytes()));
+
+        assert_eq!(expected(22, 1), part1(include_str!("../../input/2024/day22.txt")).into());
+        assert_eq!(expected(22, 1), stream_part1(include_bytes!("../../input/2024/day22.txt").as_slice()).unwrap().into());
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Some(23), reference_part2(TEST_INPUT_B));
+        assert_eq!(Ok(23), stream_part2(TEST_INPUT_B.as_bytes()));
+
+        assert_eq!(expected(22, 2), part2(include_str!("../../input/2024/day22.txt")).into());
+        assert_eq!(expected(22, 2), stream_part2(include_bytes!("../../input/2024/day22.txt").as_slice()).unwrap().into());
+    }
+}
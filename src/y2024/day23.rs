@@ -2,16 +2,29 @@
 // https://adventofcode.com/2024/day/23
 
 use std::collections::{HashMap,HashSet,LinkedList,BinaryHeap};
+use crate::error::{self, lines, Line};
 
-#[aoc(day23, part1)]
-pub fn part1(input: &str) -> u64 {
+// Computer names are two lowercase letters, packed into a u16.
+fn parse_name(line: &Line, name: &str) -> error::Result<u16> {
+    match name.as_bytes() {
+        [a, b] if a.is_ascii_lowercase() && b.is_ascii_lowercase() => Ok(u16::from_ne_bytes([*a, *b])),
+        _ => Err(line.error(name, "a two-letter computer name")),
+    }
+}
+
+/// The links between computers, as pairs of packed names.
+pub type Input = Vec<(u16, u16)>;
+
+pub fn parse(input: &str) -> error::Result<Input> {
+    lines(23, input).map(|line| {
+        let (a, b) = line.split_once(line.text, "-")?;
+        Ok((parse_name(&line, a)?, parse_name(&line, b)?))
+    }).collect()
+}
+
+pub fn solve_part1(links: &Input) -> u64 {
     let mut tuples: HashMap<u16,HashSet<u16>> = HashMap::new();
-    for line in input.lines() {
-        let mut parts = line.split("-");
-        let a: [u8; 2] = parts.next().unwrap().as_bytes().try_into().unwrap();
-        let a = u16::from_ne_bytes(a);
-        let b: [u8; 2] = parts.next().unwrap().as_bytes().try_into().unwrap();
-        let b = u16::from_ne_bytes(b);
+    for &(a, b) in links {
 
         if a & 0xFF == b't' as u16 || a < b {
             tuples.entry(a).or_default().insert(b);
@@ -51,15 +64,9 @@ pub fn part1(input: &str) -> u64 {
     total
 }
 
-#[aoc(day23, part2)]
-pub fn part2(input: &str) -> String {
+pub fn solve_part2(links: &Input) -> error::Result<String> {
     let mut tuples: HashMap<u16, HashSet<u16>> = HashMap::new();
-    for line in input.lines() {
-        let mut parts = line.split("-");
-        let a: [u8; 2] = parts.next().unwrap().as_bytes().try_into().unwrap();
-        let a = u16::from_ne_bytes(a);
-        let b: [u8; 2] = parts.next().unwrap().as_bytes().try_into().unwrap();
-        let b = u16::from_ne_bytes(b);
+    for &(a, b) in links {
 
         if a < b {
             tuples.entry(a).or_default().insert(b);
@@ -116,33 +123,42 @@ pub fn part2(input: &str) -> String {
             while !bh.is_empty() {
                 res = bh.pop().unwrap() + "," + res.as_str();
             }
-            return res;
+            return Ok(res);
         }
     }
-    panic!("no solution found");
+    Err(error::Error::new(23, 0, 0, "a set of three connected computers", "none"))
+}
+
+pub fn try_part1(input: &str) -> error::Result<u64> {
+    Ok(solve_part1(&parse(input)?))
+}
+
+pub fn try_part2(input: &str) -> error::Result<String> {
+    solve_part2(&parse(input)?)
+}
+
+#[aoc(day23, part1)]
+pub fn part1(input: &str) -> u64 {
+    try_part1(input).unwrap()
+}
+
+#[aoc(day23, part2)]
+pub fn part2(input: &str) -> String {
+    try_part2(input).unwrap()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    static TEST_INPUT: &str = "\
-        kh-tc\nqp-kh\nde-cg\nka-co\nyn-aq\nqp-ub\ncg-tb\nvc-aq\n\
-        tb-ka\nwh-tc\nyn-cg\nkh-ub\nta-co\nde-co\ntc-td\ntb-wq\n\
-        wh-td\nta-ka\ntd-qp\naq-cg\nwq-ub\nub-vc\nde-ta\nwq-aq\n\
-        wq-vc\nwh-yn\nka-de\nkh-ta\nco-tc\nwh-qp\ntb-vc\ntd-yn";
+    use crate::manifest::expected;
 
     #[test]
     fn test_part1() {
-        assert_eq!(7, part1(TEST_INPUT));
-
-        assert_eq!(1230, part1(include_str!("../input/2024/day23.txt")));
+        assert_eq!(expected(23, 1), part1(include_str!("../../input/2024/day23.txt")).into());
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!("co,de,ka,ta", part2(TEST_INPUT));
-
-        assert_eq!("az,cj,kp,lm,lt,nj,rf,rx,sn,ty,ui,wp,zo", part2(include_str!("../input/2024/day23.txt")));
+        assert_eq!(expected(23, 2), part2(include_str!("../../input/2024/day23.txt")).into());
     }
 }
@@ -0,0 +1,264 @@
+// https://adventofcode.com/2024/day/3
+
+use std::ops::Range;
+use crate::error;
+
+/// An instruction that the lexer looks for: `name(` followed by `args`
+/// numbers separated by commas, then `)`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Instruction {
+    pub name: &'static str,
+    pub args: usize,
+}
+
+/// An instruction found in the corrupted memory.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Op {
+    Mul(u64, u64),
+    Do,
+    Dont,
+    /// Any other instruction added to the lexer, with its name and arguments.
+    Other(&'static str, Vec<u64>),
+}
+
+/// An instruction and the bytes of the input that it came from.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Token {
+    pub op: Op,
+    pub span: Range<usize>,
+}
+
+/// Finds the instructions in corrupted memory. Anything that isn't exactly one
+/// of `instructions` is skipped, a byte at a time.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Lexer {
+    pub instructions: Vec<Instruction>,
+    /// The most digits that a number can have. Longer numbers, and numbers
+    /// too big for a u64, make the instruction invalid.
+    pub max_digits: usize,
+}
+
+impl Default for Lexer {
+    /// The instructions from the puzzle.
+    fn default() -> Self {
+        Lexer {
+            instructions: vec![
+                Instruction { name: "mul", args: 2 },
+                Instruction { name: "do", args: 0 },
+                Instruction { name: "don't", args: 0 },
+            ],
+            max_digits: 3,
+        }
+    }
+}
+
+impl Lexer {
+    pub fn tokens(&self, input: &str) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        let mut pos = 0;
+        while pos < input.len() {
+            match self.instructions.iter().find_map(|instr| self.token_at(input, pos, instr)) {
+                Some(token) => {
+                    pos = token.span.end;
+                    tokens.push(token);
+                }
+                None => pos += 1,
+            }
+        }
+        tokens
+    }
+
+    // The instruction `instr` starting at `start`, if it is there and valid.
+    fn token_at(&self, input: &str, start: usize, instr: &Instruction) -> Option<Token> {
+        let bytes = input.as_bytes();
+        let mut pos = start;
+        let expect = |s: &[u8], pos: &mut usize| {
+            let found = bytes[*pos..].starts_with(s);
+            if found {
+                *pos += s.len();
+            }
+            found
+        };
+        if !expect(instr.name.as_bytes(), &mut pos) || !expect(b"(", &mut pos) {
+            return None;
+        }
+        let mut args = Vec::with_capacity(instr.args);
+        for i in 0..instr.args {
+            if i > 0 && !expect(b",", &mut pos) {
+                return None;
+            }
+            let digits = bytes[pos..].iter().take_while(|b| b.is_ascii_digit()).count();
+            if !(1..=self.max_digits).contains(&digits) {
+                return None;
+            }
+            args.push(input[pos..pos + digits].parse().ok()?);
+            pos += digits;
+        }
+        if !expect(b")", &mut pos) {
+            return None;
+        }
+        let op = match (instr.name, args.as_slice()) {
+            ("mul", &[a, b]) => Op::Mul(a, b),
+            ("do", []) => Op::Do,
+            ("don't", []) => Op::Dont,
+            (name, _) => Op::Other(name, args),
+        };
+        Some(Token { op, span: start..pos })
+    }
+}
+
+/// Runs instructions one at a time, adding up the products of the `mul`s that
+/// are enabled. Other instructions are ignored, so a caller that adds its own
+/// should handle them before passing the rest on.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Interpreter {
+    /// Whether `do()` and `don't()` switch the `mul`s on and off (part 2).
+    pub conditionals: bool,
+    pub enabled: bool,
+    pub total: u64,
+}
+
+impl Interpreter {
+    pub fn new(conditionals: bool) -> Self {
+        Interpreter { conditionals, enabled: true, total: 0 }
+    }
+
+    /// Fails if the total no longer fits in a u64, which a lexer that allows
+    /// long enough numbers can lead to.
+    pub fn step(&mut self, op: &Op) -> error::Result<()> {
+        match op {
+            Op::Mul(a, b) if self.enabled || !self.conditionals => {
+                self.total = a.checked_mul(*b).and_then(|n| self.total.checked_add(n)).ok_or_else(|| {
+                    error::Error::new(3, 0, 0, "products that add up to less than 2^64", format!("`mul({},{})`", a, b))
+                })?;
+            },
+            Op::Do => self.enabled = true,
+            Op::Dont => self.enabled = false,
+            _ => {},
+        }
+        Ok(())
+    }
+
+    /// Runs all of `tokens` and returns the total.
+    pub fn run(mut self, tokens: &[Token]) -> error::Result<u64> {
+        for token in tokens {
+            self.step(&token.op)?;
+        }
+        Ok(self.total)
+    }
+}
+
+/// Copies `input` with each of `tokens` wrapped in `open` and `close`, like
+/// terminal color codes, to show where the instructions are.
+pub fn highlight(input: &str, tokens: &[Token], open: &str, close: &str) -> String {
+    let mut out = String::with_capacity(input.len() + tokens.len() * (open.len() + close.len()));
+    let mut pos = 0;
+    for token in tokens {
+        out += &input[pos..token.span.start];
+        out += open;
+        out += &input[token.span.clone()];
+        out += close;
+        pos = token.span.end;
+    }
+    out += &input[pos..];
+    out
+}
+
+pub type Input = Vec<Token>;
+
+// Every input is valid corrupted memory, so this never fails. It returns a
+// Result for consistency with the other days.
+pub fn parse(input: &str) -> error::Result<Input> {
+    Ok(Lexer::default().tokens(input))
+}
+
+pub fn solve_part1(tokens: &Input) -> error::Result<u64> {
+    Interpreter::new(false).run(tokens)
+}
+
+pub fn solve_part2(tokens: &Input) -> error::Result<u64> {
+    Interpreter::new(true).run(tokens)
+}
+
+#[aoc(day3, part1)]
+pub fn part1(input: &str) -> u64 {
+    try_part1(input).unwrap()
+}
+
+#[aoc(day3, part2)]
+pub fn part2(input: &str) -> u64 {
+    try_part2(input).unwrap()
+}
+
+pub fn try_part1(input: &str) -> error::Result<u64> {
+    solve_part1(&parse(input)?)
+}
+
+pub fn try_part2(input: &str) -> error::Result<u64> {
+    solve_part2(&parse(input)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::expected;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(expected(3, 1), part1(include_str!("../../input/2024/day3.txt")).into());
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(expected(3, 2), part2(include_str!("../../input/2024/day3.txt")).into());
+    }
+
+    #[test]
+    fn test_tokens() {
+        let input = include_str!("../../examples/2024/day3/b.txt").trim_end();
+        let tokens = parse(input).unwrap();
+        assert_eq!(vec![
+            Token { op: Op::Mul(2, 4), span: 1..9 },
+            Token { op: Op::Dont, span: 20..27 },
+            Token { op: Op::Mul(5, 5), span: 28..36 },
+            Token { op: Op::Mul(11, 8), span: 48..57 },
+            Token { op: Op::Do, span: 59..63 },
+            Token { op: Op::Mul(8, 5), span: 64..72 },
+        ], tokens);
+        assert_eq!("x[mul(2,4)]&mul[3,7]!^[don't()]_[mul(5,5)]+mul(32,64](",
+            &highlight(input, &tokens, "[", "]")[..54]);
+
+        // A failed match doesn't hide an instruction that starts inside it.
+        assert_eq!(vec![Op::Mul(2, 3)], Lexer::default().tokens("mmul(mul(2,3))").into_iter().map(|t| t.op).collect::<Vec<_>>());
+        assert_eq!(vec![Op::Do], Lexer::default().tokens("mul(1234,5)mul(1,)mul( 1,2)do()x").into_iter().map(|t| t.op).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_lexer() {
+        let mut lexer = Lexer { max_digits: 4, ..Lexer::default() };
+        lexer.instructions.push(Instruction { name: "add", args: 3 });
+        let tokens = lexer.tokens("mul(1234,5)add(1,2,3)add(1,2)don't()mul(2,2)");
+        let ops: Vec<Op> = tokens.iter().map(|t| t.op.clone()).collect();
+        assert_eq!(vec![Op::Mul(1234, 5), Op::Other("add", vec![1, 2, 3]), Op::Dont, Op::Mul(2, 2)], ops);
+        assert_eq!(11..21, tokens[1].span);
+
+        // The interpreter leaves new instructions to the caller.
+        let mut interpreter = Interpreter::new(true);
+        let mut added = 0;
+        for token in &tokens {
+            match &token.op {
+                Op::Other("add", args) => added += args.iter().sum::<u64>(),
+                op => interpreter.step(op).unwrap(),
+            }
+        }
+        assert_eq!((6170, 6, false), (interpreter.total, added, interpreter.enabled));
+        assert_eq!(Ok(6174), Interpreter::new(false).run(&tokens));
+
+        // Numbers long enough for the total to overflow.
+        let lexer = Lexer { max_digits: 20, ..Lexer::default() };
+        let tokens = lexer.tokens("mul(4294967296,4294967295)mul(4294967296,1)");
+        assert_eq!(Ok(u64::MAX - 4294967295), Interpreter::new(false).run(&tokens[..1]));
+        assert_eq!("day 3: expected products that add up to less than 2^64, found `mul(4294967296,1)`",
+            Interpreter::new(false).run(&tokens).unwrap_err().to_string());
+    }
+}
@@ -0,0 +1,211 @@
+// https://adventofcode.com/2024/day/4
+
+use crate::error;
+use crate::grid::{Grid, Pos, DELTAS8};
+
+/// A `(row, col)` offset and the letter that has to be there.
+type Cell = ((isize, isize), u8);
+
+/// Something to look for in the word search.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Pattern {
+    /// Letters in a straight line, read in any of the 8 directions.
+    Word(Vec<u8>),
+    /// Letters in a 2D shape, which can be turned by quarter turns and
+    /// mirrored. Each cell is an offset from the top left corner of the shape.
+    Template(Vec<Cell>),
+}
+
+impl Pattern {
+    pub fn word(word: &str) -> Self {
+        Pattern::Word(word.as_bytes().to_vec())
+    }
+
+    /// A template drawn as rows of text, where `.` matches anything.
+    pub fn template(rows: &str) -> Self {
+        let mut cells: Vec<Cell> = rows.lines().enumerate().flat_map(|(r, row)| {
+            row.bytes().enumerate().filter(|&(_, b)| b != b'.').map(move |(c, b)| ((r as isize, c as isize), b))
+        }).collect();
+        // Trim the rows and columns of wildcards around the shape, so that
+        // its corner is on the grid whenever all of its letters are.
+        let top = cells.iter().map(|((r, _), _)| *r).min().unwrap_or(0);
+        let left = cells.iter().map(|((_, c), _)| *c).min().unwrap_or(0);
+        for ((r, c), _) in &mut cells {
+            *r -= top;
+            *c -= left;
+        }
+        Pattern::Template(cells)
+    }
+
+    // Every way that the pattern can be placed, with its cells. Placements
+    // that cover the same shape as an earlier one are left out, so that a
+    // symmetric template only matches once in each place.
+    fn orientations(&self) -> Vec<(Orientation, Vec<Cell>)> {
+        let all: Vec<(Orientation, Vec<Cell>)> = match self {
+            Pattern::Word(word) => DELTAS8.iter().map(|&(dr, dc)| {
+                let cells = word.iter().enumerate().map(|(i, &b)| ((dr * i as isize, dc * i as isize), b)).collect();
+                (Orientation::Line((dr, dc)), cells)
+            }).collect(),
+            Pattern::Template(cells) => [false, true].into_iter().flat_map(|mirrored| (0..4).map(move |quarters| {
+                let cells = cells.iter().map(|&((r, c), b)| {
+                    let (mut r, mut c) = if mirrored { (r, -c) } else { (r, c) };
+                    for _ in 0..quarters {
+                        (r, c) = (c, -r);
+                    }
+                    ((r, c), b)
+                }).collect();
+                (Orientation::Turned { quarters, mirrored }, cells)
+            })).collect(),
+        };
+        let mut shapes: Vec<Vec<Cell>> = Vec::new();
+        all.into_iter().filter(|(_, cells)| {
+            let mut shape = cells.clone();
+            shape.sort_unstable();
+            let Some(&((r0, c0), _)) = shape.first() else {
+                return false;
+            };
+            for ((r, c), _) in &mut shape {
+                *r -= r0;
+                *c -= c0;
+            }
+            let new = !shapes.contains(&shape);
+            shapes.push(shape);
+            new
+        }).collect()
+    }
+}
+
+/// How a pattern was placed to make a match.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Orientation {
+    /// A word read in the direction of this `(row, col)` step.
+    Line((isize, isize)),
+    /// A template mirrored left to right if `mirrored`, then turned clockwise
+    /// by `quarters` quarter turns.
+    Turned { quarters: u8, mirrored: bool },
+}
+
+/// One place where a pattern was found.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Match {
+    /// The index of the pattern in the list that was searched for.
+    pub pattern: usize,
+    /// Where the first letter of a word is, or where the top left corner of a
+    /// template ended up after it was turned.
+    pub pos: Pos,
+    pub orientation: Orientation,
+}
+
+// One orientation of one pattern, with its cells relative to the first one.
+struct Placement {
+    pattern: usize,
+    orientation: Orientation,
+    cells: Vec<Cell>,
+    // Where the pattern's corner is, relative to the first cell.
+    origin: (isize, isize),
+}
+
+/// Finds all of `patterns` in one pass over the grid. Each orientation of each
+/// pattern is filed under the letter of its first cell, so only the ones that
+/// start with the letter in a cell are tried there.
+pub fn search(grid: &Grid<u8>, patterns: &[Pattern]) -> Vec<Match> {
+    let mut by_letter: Vec<Vec<Placement>> = (0..256).map(|_| Vec::new()).collect();
+    for (pattern, p) in patterns.iter().enumerate() {
+        for (orientation, cells) in p.orientations() {
+            let ((r0, c0), letter) = cells[0];
+            let cells = cells[1..].iter().map(|&((r, c), b)| ((r - r0, c - c0), b)).collect();
+            by_letter[letter as usize].push(Placement { pattern, orientation, cells, origin: (-r0, -c0) });
+        }
+    }
+
+    let mut matches = Vec::new();
+    for pos in grid.positions() {
+        for placement in &by_letter[grid[pos] as usize] {
+            if placement.cells.iter().all(|&(delta, b)| grid.offset(pos, delta).is_some_and(|p| grid[p] == b)) {
+                let origin = (pos.0.wrapping_add_signed(placement.origin.0), pos.1.wrapping_add_signed(placement.origin.1));
+                matches.push(Match { pattern: placement.pattern, pos: origin, orientation: placement.orientation });
+            }
+        }
+    }
+    matches
+}
+
+/// The word search, as bytes.
+pub type Input = Grid<u8>;
+
+pub fn parse(input: &str) -> error::Result<Input> {
+    Grid::try_parse_with(4, input, |_, b| Ok(b))
+}
+
+pub fn solve_part1(grid: &Input) -> u64 {
+    search(grid, &[Pattern::word("XMAS")]).len() as u64
+}
+
+pub fn solve_part2(grid: &Input) -> u64 {
+    search(grid, &[Pattern::template("M.S\n.A.\nM.S")]).len() as u64
+}
+
+pub fn try_part1(input: &str) -> error::Result<u64> {
+    Ok(solve_part1(&parse(input)?))
+}
+
+pub fn try_part2(input: &str) -> error::Result<u64> {
+    Ok(solve_part2(&parse(input)?))
+}
+
+#[aoc(day4, part1)]
+pub fn part1(input: &str) -> u64 {
+    try_part1(input).unwrap()
+}
+
+#[aoc(day4, part2)]
+pub fn part2(input: &str) -> u64 {
+    try_part2(input).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::expected;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(expected(4, 1), part1(include_str!("../../input/2024/day4.txt")).into());
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(expected(4, 2), part2(include_str!("../../input/2024/day4.txt")).into());
+    }
+
+    #[test]
+    fn test_search() {
+        let grid = parse("XMAS\nMASX\nAXMS\nSAMX").unwrap();
+        let matches = search(&grid, &[Pattern::word("XMAS"), Pattern::word("SAM")]);
+        assert_eq!(vec![
+            Match { pattern: 0, pos: (0, 0), orientation: Orientation::Line((0, 1)) },
+            Match { pattern: 0, pos: (0, 0), orientation: Orientation::Line((1, 0)) },
+            Match { pattern: 1, pos: (0, 3), orientation: Orientation::Line((0, -1)) },
+            Match { pattern: 1, pos: (1, 2), orientation: Orientation::Line((0, -1)) },
+            Match { pattern: 1, pos: (3, 0), orientation: Orientation::Line((-1, 0)) },
+            Match { pattern: 1, pos: (3, 0), orientation: Orientation::Line((0, 1)) },
+            Match { pattern: 0, pos: (3, 3), orientation: Orientation::Line((0, -1)) },
+        ], matches);
+
+        // The X-MAS cross has 4 different orientations, and a plus only one.
+        let cross = Pattern::template("M.S\n.A.\nM.S");
+        assert_eq!(4, cross.orientations().len());
+        let plus = Pattern::template("..\n.x.\nxxx\n.x.");
+        assert_eq!(Pattern::Template(vec![((0, 1), b'x'), ((1, 0), b'x'), ((1, 1), b'x'), ((1, 2), b'x'), ((2, 1), b'x')]), plus);
+        assert_eq!(1, plus.orientations().len());
+
+        let grid = parse("S.M.x.\n.Axxxx\nS.M.x.").unwrap();
+        let matches = search(&grid, &[cross, plus]);
+        assert_eq!(vec![
+            Match { pattern: 1, pos: (0, 3), orientation: Orientation::Turned { quarters: 0, mirrored: false } },
+            Match { pattern: 0, pos: (2, 2), orientation: Orientation::Turned { quarters: 2, mirrored: false } },
+        ], matches);
+        assert_eq!(1, Pattern::word("x").orientations().len());
+        assert!(search(&grid, &[Pattern::word(""), Pattern::template("..")]).is_empty());
+    }
+}
@@ -0,0 +1,258 @@
+// https://adventofcode.com/2024/day/5
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fmt;
+use crate::error::{self, lines};
+
+/// The page ordering rules as a graph, with an edge from each page to each
+/// page that has to come after it.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Rules {
+    after: HashMap<u32, Vec<u32>>,
+}
+
+/// Rules that contradict each other for some update: each page in the cycle
+/// has to come before the next, and the last before the first.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Cycle(pub Vec<u32>);
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, page) in self.0.iter().enumerate() {
+            let next = self.0[(i + 1) % self.0.len()];
+            write!(f, "{}{}|{}", if i > 0 { ", " } else { "" }, page, next)?;
+        }
+        Ok(())
+    }
+}
+
+// Where each page is in an update.
+fn positions(update: &[u32]) -> HashMap<u32, usize> {
+    update.iter().enumerate().map(|(i, &page)| (page, i)).collect()
+}
+
+impl Rules {
+    /// Adds the rule that `a` comes before `b`.
+    pub fn insert(&mut self, a: u32, b: u32) {
+        let after = self.after.entry(a).or_default();
+        if !after.contains(&b) {
+            after.push(b);
+        }
+    }
+
+    pub fn contains(&self, a: u32, b: u32) -> bool {
+        self.after.get(&a).is_some_and(|after| after.contains(&b))
+    }
+
+    // The pages that have to come after `page`.
+    fn after(&self, page: u32) -> &[u32] {
+        self.after.get(&page).map_or(&[], Vec::as_slice)
+    }
+
+    /// Every rule `(a, b)` that `update` breaks by having `b` before `a`,
+    /// ordered by where `a` is and then where `b` is.
+    pub fn violations(&self, update: &[u32]) -> Vec<(u32, u32)> {
+        let pos = positions(update);
+        let mut broken: Vec<(usize, usize)> = Vec::new();
+        for (i, &a) in update.iter().enumerate() {
+            broken.extend(self.after(a).iter().filter_map(|b| pos.get(b)).filter(|&&j| j < i).map(|&j| (i, j)));
+        }
+        broken.sort_unstable();
+        broken.into_iter().map(|(i, j)| (update[i], update[j])).collect()
+    }
+
+    pub fn is_ordered(&self, update: &[u32]) -> bool {
+        let pos = positions(update);
+        update.iter().enumerate().all(|(i, &a)| self.after(a).iter().all(|b| pos.get(b).is_none_or(|&j| j > i)))
+    }
+
+    /// The pages of `update` in an order that follows every rule between them,
+    /// found with a topological sort of just those pages. Pages that the rules
+    /// leave free keep their order from the update, so an update that is
+    /// already in order comes back unchanged.
+    pub fn order(&self, update: &[u32]) -> Result<Vec<u32>, Cycle> {
+        let pos = positions(update);
+        let mut before: Vec<Vec<usize>> = vec![Vec::new(); update.len()];
+        let mut after: Vec<Vec<usize>> = vec![Vec::new(); update.len()];
+        for (i, &a) in update.iter().enumerate() {
+            for &j in self.after(a).iter().filter_map(|b| pos.get(b)) {
+                after[i].push(j);
+                before[j].push(i);
+            }
+        }
+
+        // Kahn's algorithm, taking the earliest page in the update whenever
+        // there is a choice.
+        let mut waiting: Vec<usize> = before.iter().map(Vec::len).collect();
+        let mut ready: BinaryHeap<Reverse<usize>> = (0..update.len()).filter(|&i| waiting[i] == 0).map(Reverse).collect();
+        let mut order = Vec::with_capacity(update.len());
+        while let Some(Reverse(i)) = ready.pop() {
+            order.push(update[i]);
+            for &j in &after[i] {
+                waiting[j] -= 1;
+                if waiting[j] == 0 {
+                    ready.push(Reverse(j));
+                }
+            }
+        }
+        if order.len() == update.len() {
+            return Ok(order);
+        }
+
+        // Every page left is still waiting for another page that is left, so
+        // following those back from any of them has to come round in a loop.
+        let mut path: Vec<usize> = Vec::new();
+        let mut i = (0..update.len()).find(|&i| waiting[i] > 0).unwrap();
+        while !path.contains(&i) {
+            path.push(i);
+            i = *before[i].iter().find(|&&j| waiting[j] > 0).unwrap();
+        }
+        let start = path.iter().position(|&j| j == i).unwrap();
+        Err(Cycle(path[start..].iter().rev().map(|&j| update[j]).collect()))
+    }
+}
+
+/// The page ordering rules and the updates to check against them.
+pub struct Input {
+    pub rules: Rules,
+    pub updates: Vec<Vec<u32>>,
+    // The line each update is on, for errors.
+    update_lines: Vec<usize>,
+}
+
+pub fn parse(input: &str) -> error::Result<Input> {
+    let mut lines = lines(5, input);
+    let mut rules = Rules::default();
+    let mut updates: Vec<Vec<u32>> = Vec::new();
+    let mut update_lines: Vec<usize> = Vec::new();
+
+    for line in lines.by_ref() {
+        if line.text.is_empty() {
+            break;
+        }
+        let (a, b) = line.split_once(line.text, "|")?;
+        let a: u32 = line.parse(a, "a page number")?;
+        let b: u32 = line.parse(b, "a page number")?;
+        rules.insert(a, b);
+    }
+
+    for line in lines {
+        if line.text.is_empty() {
+            break;
+        }
+        let mut update: Vec<u32> = Vec::new();
+        for s in line.text.split(",") {
+            let page = line.parse(s, "a page number")?;
+            if update.contains(&page) {
+                return Err(line.error(s, "a page that isn't already in the update"));
+            }
+            update.push(page);
+        }
+        if update.len() % 2 != 1 {
+            return Err(line.error_line("an odd number of pages"));
+        }
+        updates.push(update);
+        update_lines.push(line.number);
+    }
+    Ok(Input { rules, updates, update_lines })
+}
+
+/// Each update that is out of order, by its index, with the rules it breaks.
+pub fn violations(input: &Input) -> Vec<(usize, Vec<(u32, u32)>)> {
+    input.updates.iter().enumerate()
+        .map(|(i, update)| (i, input.rules.violations(update)))
+        .filter(|(_, broken)| !broken.is_empty())
+        .collect()
+}
+
+pub fn solve_part1(input: &Input) -> u32 {
+    input.updates.iter()
+        .filter(|update| input.rules.is_ordered(update))
+        .map(|update| update[update.len()/2])
+        .sum()
+}
+
+pub fn solve_part2(input: &Input) -> error::Result<u32> {
+    let mut total = 0;
+
+    for (update, &line) in input.updates.iter().zip(&input.update_lines) {
+        if input.rules.is_ordered(update) {
+            continue;
+        }
+        let update = input.rules.order(update)
+            .map_err(|cycle| error::Error::new(5, line, 0, "rules that can put the pages in order", format!("`{}`", cycle)))?;
+        total += update[update.len()/2];
+    }
+    Ok(total)
+}
+
+pub fn try_part1(input: &str) -> error::Result<u32> {
+    Ok(solve_part1(&parse(input)?))
+}
+
+pub fn try_part2(input: &str) -> error::Result<u32> {
+    solve_part2(&parse(input)?)
+}
+
+#[aoc(day5, part1)]
+pub fn part1(input: &str) -> u32 {
+    try_part1(input).unwrap()
+}
+
+#[aoc(day5, part2)]
+pub fn part2(input: &str) -> u32 {
+    try_part2(input).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::expected;
+
+    static TEST_INPUT: &str = include_str!("../../examples/2024/day5/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!("day 5, line 3, column 3: expected a page number, found `x`",
+            try_part1("1|2\n\n1,x,2").unwrap_err().to_string());
+        assert_eq!("day 5, line 3, column 5: expected a page that isn't already in the update, found `1`",
+            try_part1("1|2\n\n1,2,1").unwrap_err().to_string());
+
+        assert_eq!(expected(5, 1), part1(include_str!("../../input/2024/day5.txt")).into());
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!("day 5, line 5: expected rules that can put the pages in order, found `1|2, 2|3, 3|1`",
+            try_part2("1|2\n2|3\n3|1\n\n3,2,1").unwrap_err().to_string());
+
+        assert_eq!(expected(5, 2), part2(include_str!("../../input/2024/day5.txt")).into());
+    }
+
+    #[test]
+    fn test_rules() {
+        let input = parse(TEST_INPUT).unwrap();
+        let rules = &input.rules;
+        assert!(rules.contains(47, 53) && !rules.contains(53, 47));
+        assert_eq!(vec![
+            (3, vec![(97, 75)]),
+            (4, vec![(29, 13)]),
+            (5, vec![(75, 13), (29, 13), (47, 13), (47, 29)]),
+        ], violations(&input));
+        assert_eq!(Ok(vec![97, 75, 47, 61, 53]), rules.order(&input.updates[3]));
+        assert_eq!(Ok(vec![97, 75, 47, 29, 13]), rules.order(&input.updates[5]));
+        assert_eq!(Ok(input.updates[0].clone()), rules.order(&input.updates[0]));
+
+        // Pages without rules between them keep their order.
+        assert_eq!(Ok(vec![5, 1, 75, 47, 61]), rules.order(&[5, 61, 1, 75, 47]));
+
+        let mut rules = Rules::default();
+        for (a, b) in [(1, 2), (2, 3), (3, 4), (4, 2), (4, 5)] {
+            rules.insert(a, b);
+        }
+        assert_eq!(Err(Cycle(vec![2, 3, 4])), rules.order(&[5, 4, 3, 2, 1]));
+        assert_eq!("2|3, 3|4, 4|2", Cycle(vec![2, 3, 4]).to_string());
+        assert_eq!(Ok(vec![5, 1, 2, 3]), rules.order(&[5, 3, 2, 1]));
+    }
+}
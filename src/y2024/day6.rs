@@ -0,0 +1,171 @@
+
+// https://adventofcode.com/2024/day/6
+
+use std::collections::HashSet;
+use rayon::prelude::*;
+use crate::error::{self, Error};
+use crate::grid::{Direction, Grid, Pos};
+use crate::parallel;
+use crate::viz;
+
+fn walk_length(obstacles: &Grid<bool>,
+        extra_obstacle: &Pos,
+        guard_start_pos: &Pos,
+        guards: &mut Option<HashSet<Pos>>) -> u64 {
+    let mut guard_dir = Direction::North;
+    let mut guard_pos: Pos = *guard_start_pos;
+    // This is a position and a direction tuple. It updates to the latest guard
+    // pos/dir at turns, less frequently over time. Eventually, it will match
+    // the current pos/dir if there is a loop.
+    let mut loop_detect = (guard_pos, guard_dir);
+    let mut loop_counter = 0;
+    let mut loop_length = 1;
+    if let Some(guards_set) = guards {
+        guards_set.insert(guard_pos);
+    }
+    
+    while let Some(new_guard_pos) = obstacles.step(guard_pos, guard_dir) {
+        if obstacles[new_guard_pos] || new_guard_pos == *extra_obstacle {
+            // Rotate 90 degrees.
+            guard_dir = guard_dir.turn_right();
+            // Loop detection
+            if loop_detect == (guard_pos, guard_dir) {
+                return 1;
+            }
+            if loop_counter == loop_length {
+                loop_length *= 2;
+                loop_counter = 0;
+                loop_detect = (guard_pos, guard_dir);
+            }
+            loop_counter += 1;
+        } else {
+            guard_pos = new_guard_pos;
+            if let Some(guards_set) = guards {
+                guards_set.insert(guard_pos);
+            }
+        }
+    }
+
+    0
+}
+
+/// Where the obstacles are, and where the guard starts.
+pub type Input = (Grid<bool>, Pos);
+
+pub fn parse(input: &str) -> error::Result<Input> {
+    let mut guard_pos: Option<Pos> = None;
+    let obstacles = Grid::try_parse_with(6, input, |pos, b| {
+        match b {
+            b'#' => Ok(true),
+            b'^' => {
+                guard_pos = Some(pos);
+                Ok(false)
+            },
+            b'.' => Ok(false),
+            _ => Err("one of `.`, `#` or `^`"),
+        }
+    })?;
+    let guard_pos = guard_pos.ok_or_else(|| Error::new(6, 0, 0, "a guard (`^`)", "none"))?;
+    Ok((obstacles, guard_pos))
+}
+
+pub fn solve_part1((obstacles, guard_pos): &Input) -> u64 {
+    let mut guards: Option<HashSet<Pos>> = Some(HashSet::with_capacity(5000));
+    walk_length(obstacles, &(usize::MAX, usize::MAX), guard_pos, &mut guards);
+    guards.unwrap().len() as u64
+}
+
+pub fn solve_part2((obstacles, guard_pos): &Input) -> u64 {
+    let mut guards: Option<HashSet<Pos>> = Some(HashSet::with_capacity(5000));
+    walk_length(obstacles, &(usize::MAX, usize::MAX), guard_pos, &mut guards);
+
+    let guards = guards.unwrap();
+    parallel::install(|| guards.par_iter().map(|guard| -> u64 {
+        if guard == guard_pos {
+            return 0;
+        }
+        // TODO: This replays the full walk for each possible guard pos. Instead
+        // consider starting from the point in the original walk where the new
+        // guard position was first considered.
+        walk_length(obstacles, guard, guard_pos, &mut None)
+    }).sum())
+}
+
+pub fn try_part1(input: &str) -> error::Result<u64> {
+    Ok(solve_part1(&parse(input)?))
+}
+
+pub fn try_part2(input: &str) -> error::Result<u64> {
+    Ok(solve_part2(&parse(input)?))
+}
+
+/// Records the guard's walk, one frame per step, ending when the guard leaves
+/// the map or starts going round in a loop.
+pub fn record(input: &str, rec: &mut viz::Recorder) -> error::Result<()> {
+    let (obstacles, mut guard_pos) = parse(input)?;
+    let mut guard_dir = Direction::North;
+    let mut seen = HashSet::new();
+    let mut visited = Grid::new(obstacles.width(), obstacles.height(), false);
+    let frame = |visited: &Grid<bool>, guard_pos: Pos| Grid::from_fn(obstacles.width(), obstacles.height(), |pos| {
+        if pos == guard_pos {
+            viz::ACTOR
+        } else if obstacles[pos] {
+            viz::WALL
+        } else if visited[pos] {
+            viz::VISITED
+        } else {
+            viz::EMPTY
+        }
+    });
+    while seen.insert((guard_pos, guard_dir)) {
+        visited[guard_pos] = true;
+        rec.step(|| frame(&visited, guard_pos));
+        match obstacles.step(guard_pos, guard_dir) {
+            Some(pos) if obstacles[pos] => guard_dir = guard_dir.turn_right(),
+            Some(pos) => guard_pos = pos,
+            None => break,
+        }
+    }
+    rec.finish(frame(&visited, guard_pos));
+    Ok(())
+}
+
+#[aoc(day6, part1)]
+pub fn part1(input: &str) -> u64 {
+    try_part1(input).unwrap()
+}
+
+#[aoc(day6, part2)]
+pub fn part2(input: &str) -> u64 {
+    try_part2(input).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::expected;
+
+    static TEST_INPUT: &str = include_str!("../../examples/2024/day6/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!("day 6: expected a guard (`^`), found none", try_part1("..\n..").unwrap_err().to_string());
+
+        assert_eq!(expected(6, 1), part1(include_str!("../../input/2024/day6.txt")).into());
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(expected(6, 2), part2(include_str!("../../input/2024/day6.txt")).into());
+    }
+
+    #[test]
+    fn test_record() {
+        let mut rec = viz::Recorder::new(1000);
+        record(TEST_INPUT, &mut rec).unwrap();
+        let last = rec.frames().last().unwrap();
+        // The guard is on the bottom edge, about to leave.
+        assert_eq!(viz::ACTOR, last[(9, 7)]);
+        assert_eq!(41, last.iter().filter(|(_, &c)| c != viz::EMPTY && c != viz::WALL).count());
+    }
+}
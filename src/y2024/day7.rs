@@ -0,0 +1,154 @@
+
+// https://adventofcode.com/2024/day/7
+
+use std::io::BufRead;
+use crate::error::{self, lines};
+use crate::stream::LineReader;
+
+fn parse_line(line: &error::Line) -> error::Result<(u64, Vec<u64>)> {
+    let (res, vals) = line.split_once(line.text, ":")?;
+    let res: u64 = line.parse(res, "a test value")?;
+    let vals: Vec<u64> = vals.split_whitespace()
+        .map(|p| line.parse(p, "a number"))
+        .collect::<error::Result<Vec<u64>>>()?;
+    if vals.is_empty() {
+        return Err(line.error(&line.text[line.text.len()..], "a number"));
+    }
+    // The solver divides by each value.
+    if res == 0 || vals.contains(&0) {
+        return Err(line.error_line("only positive numbers"));
+    }
+    Ok((res, vals))
+}
+
+/// Each equation's test value and the numbers that should make it.
+pub type Input = Vec<(u64, Vec<u64>)>;
+
+pub fn parse(input: &str) -> error::Result<Input> {
+    lines(7, input)
+        .take_while(|line| !line.text.is_empty())
+        .map(|line| parse_line(&line))
+        .collect()
+}
+
+pub fn solve_part1(equations: &Input) -> u64 {
+    equations.iter().filter(|(res, vals)| has_solution2(*res, vals)).map(|(res, _)| res).sum()
+}
+
+pub fn solve_part2(equations: &Input) -> u64 {
+    equations.iter().filter(|(res, vals)| has_solution3(*res, vals)).map(|(res, _)| res).sum()
+}
+
+pub fn try_part1(input: &str) -> error::Result<u64> {
+    Ok(solve_part1(&parse(input)?))
+}
+
+pub fn try_part2(input: &str) -> error::Result<u64> {
+    Ok(solve_part2(&parse(input)?))
+}
+
+// Checks one equation at a time, so only the current line is in memory.
+fn stream_total(input: impl BufRead, has_solution: fn(u64, &[u64]) -> bool) -> error::Result<u64> {
+    let mut total: u64 = 0;
+    let mut reader = LineReader::new(7, input);
+    while let Some(line) = reader.next_line()? {
+        if line.text.is_empty() {
+            break;
+        }
+        let (res, vals) = parse_line(&line)?;
+        if has_solution(res, &vals) {
+            total += res;
+        }
+    }
+    Ok(total)
+}
+
+pub fn stream_part1(input: impl BufRead) -> error::Result<u64> {
+    stream_total(input, has_solution2)
+}
+
+pub fn stream_part2(input: impl BufRead) -> error::Result<u64> {
+    stream_total(input, has_solution3)
+}
+
+#[aoc(day7, part1)]
+pub fn part1(input: &str) -> u64 {
+    try_part1(input).unwrap()
+}
+
+#[aoc(day7, part2)]
+pub fn part2(input: &str) -> u64 {
+    try_part2(input).unwrap()
+}
+
+// TODO: Merge into has_solution3
+// TODO: Move res into vals[0]
+// TODO: Return res or 0 instead of bool
+fn has_solution2(res: u64, vals: &[u64]) -> bool {
+    assert!(!vals.is_empty());
+    assert!(res > 0);
+    assert!(vals[vals.len()-1] > 0);
+
+    if vals.len() == 1 {
+        return res == vals[0];
+    }
+    if res.is_multiple_of(vals[vals.len()-1]) && has_solution2(res / vals[vals.len()-1], &vals[0..vals.len()-1]) {
+        return true;
+    }
+    if res > vals[vals.len()-1] && has_solution2(res - vals[vals.len()-1], &vals[0..vals.len()-1]) {
+        return true;
+    }
+    false
+}
+
+fn has_solution3(res: u64, vals: &[u64]) -> bool {
+    assert!(!vals.is_empty());
+    assert!(res > 0);
+    assert!(vals[vals.len()-1] > 0);
+
+    if vals.len() == 1 {
+        return res == vals[0];
+    }
+    if res.is_multiple_of(vals[vals.len()-1]) && has_solution3(res / vals[vals.len()-1], &vals[0..vals.len()-1]) {
+        return true;
+    }
+    if res > vals[vals.len()-1] {
+        if has_solution3(res - vals[vals.len()-1], &vals[0..vals.len()-1]) {
+            return true;
+        }
+        // TODO: This can be done without converting to strings
+        let vs = vals[vals.len()-1].to_string();
+        let rs = res.to_string();
+        if rs.ends_with(&vs) {
+            let new_rs = rs[..rs.len()-vs.len()].parse::<u64>().unwrap();
+            if has_solution3(new_rs, &vals[0..vals.len()-1]) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::expected;
+
+    static TEST_INPUT: &str = include_str!("../../examples/2024/day7/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Ok(3749), stream_part1(TEST_INPUT.as_bytes()));
+
+        assert_eq!(expected(7, 1), part1(include_str!("../../input/2024/day7.txt")).into());
+        assert_eq!(expected(7, 1), stream_part1(include_bytes!("../../input/2024/day7.txt").as_slice()).unwrap().into());
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Ok(11387), stream_part2(TEST_INPUT.as_bytes()));
+
+        assert_eq!(expected(7, 2), part2(include_str!("../../input/2024/day7.txt")).into());
+        assert_eq!(expected(7, 2), stream_part2(include_bytes!("../../input/2024/day7.txt").as_slice()).unwrap().into());
+    }
+}
@@ -0,0 +1,114 @@
+
+// https://adventofcode.com/2024/day/8
+
+use std::collections::{HashMap,HashSet};
+use crate::error;
+use crate::grid::{Grid, Pos};
+
+/// The map, and the positions of the antennas grouped by frequency.
+pub struct Input {
+    pub grid: Grid<u8>,
+    pub antennas: HashMap<u8, Vec<Pos>>,
+}
+
+pub fn parse(input: &str) -> error::Result<Input> {
+    let grid: Grid<u8> = Grid::try_parse_with(8, input, |_, b| {
+        if b.is_ascii_graphic() { Ok(b) } else { Err("an antenna or `.`") }
+    })?;
+    let mut antennas: HashMap<u8, Vec<Pos>> = HashMap::new();
+    for (pos, b) in grid.iter() {
+        if *b != b'.' {
+            // Store the position of each antenna, grouped by ID. This will
+            // allow for efficient processing later.
+            antennas.entry(*b).or_default().push(pos);
+        }
+    }
+    Ok(Input { grid, antennas })
+}
+
+pub fn solve_part1(input: &Input) -> u64 {
+    count_antinodes(input, false)
+}
+
+pub fn solve_part2(input: &Input) -> u64 {
+    count_antinodes(input, true)
+}
+
+pub fn try_part1(input: &str) -> error::Result<u64> {
+    Ok(solve_part1(&parse(input)?))
+}
+
+pub fn try_part2(input: &str) -> error::Result<u64> {
+    Ok(solve_part2(&parse(input)?))
+}
+
+#[aoc(day8, part1)]
+pub fn part1(input: &str) -> u64 {
+    try_part1(input).unwrap()
+}
+
+#[aoc(day8, part2)]
+pub fn part2(input: &str) -> u64 {
+    try_part2(input).unwrap()
+}
+
+fn count_antinodes(Input { grid, antennas }: &Input, extend: bool) -> u64 {
+    let mut antinodes: HashSet<Pos> = HashSet::new();
+    for (_, points) in antennas.iter() {
+        if points.len() < 2 {
+            continue;
+        }
+        // Note: the instructions indicate that any gridpoint along the line
+        // counts as an antinode. However, this implementation (which produces
+        // an accepted answer), does not count gridpoints that are not a
+        // multiple of the distance from either point. IMO a spec-compliant
+        // implementation should have to count intermediate gridpoints if dx and
+        // dy are not coprime.
+        // TODO: Assert that dx and dy are coprime otherwise print an error
+        // explaining this issue.
+        for i in 0..points.len() {
+            for j in 0..points.len() {
+                // Walk in one direction (from i towards j and beyond). The
+                // other direction will be walked in another iteration when i
+                // and j are reversed.
+                if i == j {
+                    continue;
+                }
+                let p1 = points[i];
+                let mut p2 = points[j];
+                let delta = (p2.0 as isize - p1.0 as isize, p2.1 as isize - p1.1 as isize);
+                if extend {
+                    antinodes.insert(p2);
+                }
+                // Select the next point along the line. Terminate if the
+                // point is outside the grid.
+                while let Some(p) = grid.offset(p2, delta) {
+                    p2 = p;
+                    antinodes.insert(p2);
+                    // Additional gridpoints are only added in part 2.
+                    if !extend {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    antinodes.len() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::expected;
+    
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(expected(8, 1), part1(include_str!("../../input/2024/day8.txt")).into());
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(expected(8, 2), part2(include_str!("../../input/2024/day8.txt")).into());
+    }
+}
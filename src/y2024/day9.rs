@@ -0,0 +1,248 @@
+
+// https://adventofcode.com/2024/day/9
+
+use std::cmp::min;
+use crate::error::{self, lines};
+
+/// The length of each file and each free space, alternating and starting with
+/// a file.
+pub type Input = Vec<u8>;
+
+// The solvers below don't check their input, so check that it is a single line
+// of digits up front. They also assume that every file has at least one block,
+// which solve_part2() underflows without.
+pub fn parse(input: &str) -> error::Result<Input> {
+    let input = input.trim_ascii_end();
+    let line = lines(9, input).next().ok_or_else(|| error::end_of_input(9, input, "a disk map"))?;
+    if let Some(i) = line.text.bytes().position(|b| !b.is_ascii_digit()) {
+        return Err(line.error(&line.text[i..], "a digit"));
+    }
+    if let Some(i) = line.text.bytes().step_by(2).position(|b| b == b'0') {
+        return Err(line.error(&line.text[2 * i..], "a file length from 1 to 9"));
+    }
+    Ok(lengths(input))
+}
+
+fn lengths(input: &str) -> Vec<u8> {
+    input.trim_ascii_end().bytes().map(|b| b.wrapping_sub(b'0')).collect()
+}
+
+pub fn try_part1(input: &str) -> error::Result<u64> {
+    Ok(solve_part1(&parse(input)?))
+}
+
+pub fn try_part2(input: &str) -> error::Result<u64> {
+    Ok(solve_part2(&parse(input)?))
+}
+
+// These skip parse()'s checks.
+#[aoc(day9, part1)]
+pub fn part1(input: &str) -> u64 {
+    solve_part1(&lengths(input))
+}
+
+#[aoc(day9, part2)]
+pub fn part2(input: &str) -> u64 {
+    solve_part2(&lengths(input))
+}
+
+pub fn solve_part1(input: &Input) -> u64 {
+    let mut in_pos: u32 = 0;
+    let mut in_end: u32 = (input.len()-1) as u32;
+    if !in_end.is_multiple_of(2) {
+        in_end -= 1;
+    }
+    let mut out_pos: u32 = 0;
+    let mut consumed_end: u32 = 0;
+    let mut consumed_start: u32 = 0;
+    let mut total: u64 = 0;
+    loop {
+        if in_end <= in_pos {
+            if in_end < in_pos {
+                break;
+            }
+            assert_eq!(in_end, in_pos);
+            consumed_start = consumed_end;
+        }
+        let space: u32 = input[in_pos as usize] as u32 - consumed_start;
+        assert!(space <= 9);
+        if space == 0 {
+            // If the whole space was consumed on the last iteration, in_pos
+            // should have already advanced and consumed_start should have been
+            // reset.
+            assert_eq!(consumed_start, 0);
+            in_pos += 1;
+            continue;
+        }
+        if in_pos.is_multiple_of(2) {
+            // Count the file that is not moving.
+            let id: u32 = in_pos / 2;
+            let consume: u32 = space;
+            assert!(consume <= 9);
+            let consume_sum: u32 = (consume * (consume - 1)) / 2 + consume * out_pos;
+            total += id as u64 * consume_sum as u64;
+        
+            out_pos += consume;
+
+            in_pos += 1;
+        } else {
+            // Count the file that is moving.
+            // in_end is always managed to be even.
+            assert_eq!(in_end % 2, 0);
+            let id: u32 = in_end / 2;
+            let fill: u32 = input[in_end as usize] as u32 - consumed_end;
+            assert!(fill <= 9);
+            let consume: u32 = min(space, fill);
+            assert!(consume <= 9);
+            let consume_sum: u32 = (consume * (consume - 1)) / 2 + consume * out_pos;
+            total += id as u64 * consume_sum as u64;
+            out_pos += consume;
+
+            consumed_start += consume;
+            consumed_end += consume;
+
+            if fill >= space {
+                assert_eq!(consume, space);
+                in_pos += 1;
+                consumed_start = 0;
+            }
+            if space >= fill {
+                assert_eq!(consume, fill);
+                in_end -= 2;
+                consumed_end = 0;
+            }
+        }
+    }
+
+    total
+}
+
+pub fn solve_part2(input: &Input) -> u64 {
+    let mut blocks: Vec<(u32, u32)> = Vec::with_capacity(input.len().div_ceil(2));
+    let mut spaces: Vec<(u32, u32)> = Vec::with_capacity(input.len() / 2);
+    let mut space_cache: [usize; 10] = [0; 10];
+    let mut pos: u32 = 0;
+    let mut total: u64 = 0;
+
+    for (i, b) in input.iter().enumerate() {
+        let len: u32 = *b as u32;
+        assert!(len < 10);
+        if i % 2 == 0 {
+            blocks.push((pos, len));
+        } else {
+            spaces.push((pos, len));
+        }
+        pos += len;
+    }
+
+    for (id, (pos, len)) in blocks.iter().rev().enumerate() {
+        let id = blocks.len() - id - 1;
+        let mut new_pos = *pos;
+        let cache: &mut usize = &mut space_cache[*len as usize];
+        // TODO: Borrow from other caches?
+        while *cache < id && spaces[*cache].1 < *len {
+            *cache += 1;
+        }
+        if *cache < id {
+            let space: &mut (u32, u32) = &mut spaces[*cache];
+            new_pos = space.0;
+            space.0 += *len;
+            space.1 -= *len;
+        }
+        let consume_sum: u32 = (*len * (*len - 1)) / 2 + *len * new_pos;
+        total += id as u64 * consume_sum as u64;
+    }
+
+    total
+}
+
+// Lays out every block of the disk map, with the ID of the file in it. Used
+// by the reference versions of the parts below.
+fn reference_blocks(input: &str) -> Option<Vec<Option<u64>>> {
+    let lengths = parse(input).ok()?;
+    let mut blocks = Vec::new();
+    for (i, len) in lengths.into_iter().enumerate() {
+        let id = (i % 2 == 0).then_some(i as u64 / 2);
+        blocks.extend(std::iter::repeat_n(id, len as usize));
+    }
+    Some(blocks)
+}
+
+fn reference_checksum(blocks: &[Option<u64>]) -> u64 {
+    blocks.iter().enumerate().filter_map(|(pos, id)| id.map(|id| pos as u64 * id)).sum()
+}
+
+// A straightforward version of part1(), which moves one block at a time. This
+// is what part1() is checked against in crate::diff.
+pub(crate) fn reference_part1(input: &str) -> Option<u64> {
+    let mut blocks = reference_blocks(input)?;
+    let mut free = 0;
+    let mut last = blocks.len();
+    loop {
+        while free < last && blocks[free].is_some() {
+            free += 1;
+        }
+        while free < last && blocks[last - 1].is_none() {
+            last -= 1;
+        }
+        if free + 1 >= last {
+            break;
+        }
+        blocks.swap(free, last - 1);
+    }
+    Some(reference_checksum(&blocks))
+}
+
+// A straightforward version of part2(), which searches the whole disk for space
+// for each file.
+pub(crate) fn reference_part2(input: &str) -> Option<u64> {
+    let mut blocks = reference_blocks(input)?;
+    let files = blocks.iter().flatten().max().map_or(0, |id| id + 1);
+    for id in (0..files).rev() {
+        let start = blocks.iter().position(|b| *b == Some(id)).unwrap();
+        let len = blocks[start..].iter().take_while(|b| **b == Some(id)).count();
+        let space = (0..start).find(|&i| {
+            i + len <= start && blocks[i..i + len].iter().all(Option::is_none)
+        });
+        if let Some(space) = space {
+            for i in 0..len {
+                blocks.swap(space + i, start + i);
+            }
+        }
+    }
+    Some(reference_checksum(&blocks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::expected;
+
+    static TEST_INPUT: &str = include_str!("../../examples/2024/day9/example.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(1928, part1(TEST_INPUT.trim_end()));
+        assert_eq!(Some(1928), reference_part1(TEST_INPUT));
+
+        assert_eq!(expected(9, 1), part1(include_str!("../../input/2024/day9.txt")).into());
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(2858, part2(TEST_INPUT.trim_end()));
+        assert_eq!(Some(2858), reference_part2(TEST_INPUT));
+        assert_eq!("day 9, line 1, column 3: expected a file length from 1 to 9, found `0`",
+            try_part2("120").unwrap_err().to_string());
+
+        assert_eq!(expected(9, 2), part2(include_str!("../../input/2024/day9.txt")).into());
+    }
+
+    #[test]
+    fn test_large() {
+        // Twice the size of a real input, where the checksums used to overflow.
+        let input = crate::gen::day9(&mut crate::gen::Rng::new(1), 20000);
+        assert_eq!(reference_part1(&input), Some(part1(&input)));
+        assert_eq!(49867401601599, part2(&input));
+    }
+}